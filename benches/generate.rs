@@ -0,0 +1,42 @@
+//! Times generating large mazes with every algorithm, run with `cargo bench`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use maze_lite::maze::{generate, MazeAlgorithm, MazeConfig, MazeSeed};
+
+const SIZE: u32 = 256;
+const RUNS: u64 = 10;
+
+fn main() {
+    println!("{SIZE}x{SIZE} cells, {RUNS} runs per algorithm\n");
+    println!("{:<12} {:>10} {:>10}", "algorithm", "mean ms", "max ms");
+
+    for algorithm in MazeAlgorithm::ALL {
+        let config = MazeConfig {
+            width: SIZE,
+            height: SIZE,
+            algorithm,
+            ..Default::default()
+        };
+
+        let times = (0..RUNS)
+            .map(|seed| {
+                let start = Instant::now();
+                black_box(generate(&config, &mut MazeSeed(seed).rng()));
+                start.elapsed()
+            })
+            .collect::<Vec<_>>();
+
+        let mean = times.iter().sum::<Duration>() / RUNS as u32;
+        let max = times.iter().max().copied().unwrap_or_default();
+        println!(
+            "{:<12} {:>10.2} {:>10.2}",
+            algorithm.name(),
+            mean.as_secs_f64() * 1000.,
+            max.as_secs_f64() * 1000.
+        );
+    }
+}
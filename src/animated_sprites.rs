@@ -1,70 +1,322 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use std::ops::Range;
 
 pub struct AnimatedSpritePlugin;
 
 impl Plugin for AnimatedSpritePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, animate_sprite);
+        app.add_event::<AnimationFinished>()
+            .add_systems(Update, animate_sprite);
     }
 }
 
 #[derive(Debug, Bundle)]
 pub struct AnimatedSpriteBundle {
-    animation: SpriteAnimation,
-    sprite: SpriteBundle,
-    atlas: TextureAtlas,
+    pub controller: AnimationController,
+    pub sprite: SpriteBundle,
+    pub atlas: TextureAtlas,
 }
 
-#[derive(Debug, Component)]
-struct SpriteAnimation {
-    speed: f32,
+/// What a clip does once it reaches its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Starts over from the first frame.
+    #[default]
+    Loop,
+    /// Plays once, then goes back to the first frame and stops.
+    Once,
+    /// Plays forwards then backwards forever.
+    PingPong,
+    /// Plays once, then stops on the last frame.
+    HoldLast,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteClip {
+    /// Atlas indices of the frames in this clip.
+    pub frames: Range<u32>,
+    /// Frames per second.
+    pub fps: f32,
+    pub mode: LoopMode,
+    /// Clip to switch to once a [`LoopMode::Once`] or [`LoopMode::HoldLast`] clip finishes.
+    pub next: Option<String>,
+}
+
+impl SpriteClip {
+    pub fn new(frames: Range<u32>, fps: f32) -> Self {
+        Self {
+            frames,
+            fps: fps.max(0.),
+            mode: LoopMode::default(),
+            next: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: LoopMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_next(mut self, next: impl Into<String>) -> Self {
+        self.next = Some(next.into());
+        self
+    }
+
+    fn len(&self) -> u32 {
+        self.frames.len() as u32
+    }
+}
+
+/// Sent when a [`LoopMode::Once`] or [`LoopMode::HoldLast`] clip plays its last frame.
+#[derive(Event, Debug, Clone)]
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub clip: String,
+}
+
+/// Plays named [`SpriteClip`]s on a [`TextureAtlas`].
+#[derive(Debug, Component, Clone)]
+pub struct AnimationController {
+    clips: HashMap<String, SpriteClip>,
+    current: String,
+    /// Offset into the current clip's frames.
+    frame: u32,
     accumulator: f32,
-    range: Range<u32>,
-    current_frame: i32,
-    transform: Transform,
+    /// Whether a ping-pong clip is currently playing backwards.
+    reversed: bool,
+    finished: bool,
 }
 
-impl SpriteAnimation {
-    pub fn new(range: Range<u32>, speed: f32, transform: Transform) -> Self {
+impl AnimationController {
+    /// Creates a controller that starts out playing `clip`.
+    pub fn new(name: impl Into<String>, clip: SpriteClip) -> Self {
+        let name = name.into();
+
         Self {
-            current_frame: range.start as i32,
-            speed: speed.max(0.),
-            range,
-            transform,
+            clips: HashMap::from([(name.clone(), clip)]),
+            current: name,
+            frame: 0,
             accumulator: 0.,
+            reversed: false,
+            finished: false,
+        }
+    }
+
+    pub fn with_clip(mut self, name: impl Into<String>, clip: SpriteClip) -> Self {
+        self.clips.insert(name.into(), clip);
+        self
+    }
+
+    /// Switches to the clip called `name`, starting from its first frame.
+    ///
+    /// Does nothing if `name` is already playing, so this can be called every frame.
+    /// Returns whether `name` is playing afterwards.
+    pub fn play(&mut self, name: &str) -> bool {
+        if self.current == name {
+            return true;
         }
+
+        if !self.clips.contains_key(name) {
+            warn!("no animation clip named {name:?}");
+            return false;
+        }
+
+        self.current = name.to_owned();
+        self.restart();
+        true
+    }
+
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.accumulator = 0.;
+        self.reversed = false;
+        self.finished = false;
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
     }
 
-    pub fn set_speed(&mut self, new_speed: f32) {
-        self.speed = new_speed;
+    pub fn is_finished(&self) -> bool {
+        self.finished
     }
 
-    pub fn advance(&mut self, time: &Time) {
-        self.accumulator += self.speed.abs() * time.delta_seconds();
+    fn clip(&self) -> &SpriteClip {
+        &self.clips[&self.current]
+    }
+
+    /// The atlas index of the current frame.
+    pub fn atlas_index(&self) -> usize {
+        (self.clip().frames.start + self.frame) as usize
+    }
+
+    /// Advances the current clip, returns the name of the clip if it finished.
+    pub fn advance(&mut self, delta_seconds: f32) -> Option<String> {
+        if self.finished {
+            return None;
+        }
 
-        let frame_increment = if self.speed >= 0. { 1 } else { -1 };
+        let clip = self.clip().clone();
+        self.accumulator += clip.fps * delta_seconds;
 
         while self.accumulator >= 1. {
             self.accumulator -= 1.;
 
-            self.current_frame += frame_increment;
+            let last = clip.len().saturating_sub(1);
+            match clip.mode {
+                LoopMode::Loop => {
+                    self.frame = if self.frame >= last {
+                        0
+                    } else {
+                        self.frame + 1
+                    };
+                }
+                LoopMode::PingPong => {
+                    if last == 0 {
+                        continue;
+                    }
+
+                    if (self.reversed && self.frame == 0) || (!self.reversed && self.frame >= last)
+                    {
+                        self.reversed = !self.reversed;
+                    }
+                    self.frame = if self.reversed {
+                        self.frame - 1
+                    } else {
+                        self.frame + 1
+                    };
+                }
+                LoopMode::Once | LoopMode::HoldLast => {
+                    if self.frame < last {
+                        self.frame += 1;
+                        continue;
+                    }
 
-            if self.current_frame >= self.range.end as i32 {
-                self.current_frame = self.range.start as i32;
-            } else if self.current_frame <= self.range.start as i32 {
-                self.current_frame = self.range.end as i32 - 1;
+                    // A clip that can't be switched to, or is this one, ends here instead.
+                    let finished = self.current.clone();
+                    let next = clip.next.as_deref().filter(|next| *next != finished);
+                    if !next.is_some_and(|next| self.play(next)) {
+                        if clip.mode == LoopMode::Once {
+                            self.frame = 0;
+                        }
+                        self.finished = true;
+                    }
+
+                    return Some(finished);
+                }
             }
         }
+
+        None
     }
 }
 
 fn animate_sprite(
     time: Res<Time>,
-    mut animated_sprites: Query<(&mut TextureAtlas, &mut SpriteAnimation)>,
+    mut animated_sprites: Query<(Entity, &mut TextureAtlas, &mut AnimationController)>,
+    mut finished: EventWriter<AnimationFinished>,
 ) {
-    for (mut atlas, mut animation) in animated_sprites.iter_mut() {
-        atlas.index = animation.current_frame as usize;
-        animation.advance(&time);
+    for (entity, mut atlas, mut controller) in animated_sprites.iter_mut() {
+        if let Some(clip) = controller.advance(time.delta_seconds()) {
+            finished.send(AnimationFinished { entity, clip });
+        }
+
+        atlas.index = controller.atlas_index();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Advances one frame at a time, returning the atlas index after each frame and the
+    /// clips that finished along the way.
+    fn run(controller: &mut AnimationController, frames: usize) -> (Vec<usize>, Vec<String>) {
+        let mut indices = Vec::new();
+        let mut finished = Vec::new();
+        for _ in 0..frames {
+            finished.extend(controller.advance(1.));
+            indices.push(controller.atlas_index());
+        }
+        (indices, finished)
+    }
+
+    #[test]
+    fn loop_starts_over() {
+        let mut controller = AnimationController::new("walk", SpriteClip::new(4..7, 1.));
+        let (indices, finished) = run(&mut controller, 5);
+        assert_eq!(indices, [5, 6, 4, 5, 6]);
+        assert!(finished.is_empty());
+        assert!(!controller.is_finished());
+    }
+
+    #[test]
+    fn once_resets_to_the_first_frame() {
+        let clip = SpriteClip::new(0..3, 1.).with_mode(LoopMode::Once);
+        let mut controller = AnimationController::new("attack", clip);
+        let (indices, finished) = run(&mut controller, 5);
+        assert_eq!(indices, [1, 2, 0, 0, 0]);
+        assert_eq!(finished, ["attack"]);
+        assert!(controller.is_finished());
+    }
+
+    #[test]
+    fn hold_last_stays_on_the_last_frame() {
+        let clip = SpriteClip::new(0..3, 1.).with_mode(LoopMode::HoldLast);
+        let mut controller = AnimationController::new("death", clip);
+        let (indices, finished) = run(&mut controller, 5);
+        assert_eq!(indices, [1, 2, 2, 2, 2]);
+        assert_eq!(finished, ["death"]);
+        assert!(controller.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_bounces_between_the_ends() {
+        let clip = SpriteClip::new(10..13, 1.).with_mode(LoopMode::PingPong);
+        let mut controller = AnimationController::new("idle", clip);
+        let (indices, finished) = run(&mut controller, 6);
+        assert_eq!(indices, [11, 12, 11, 10, 11, 12]);
+        assert!(finished.is_empty());
+    }
+
+    #[test]
+    fn next_chains_into_another_clip() {
+        let hit = SpriteClip::new(0..2, 1.)
+            .with_mode(LoopMode::Once)
+            .with_next("idle");
+        let mut controller =
+            AnimationController::new("hit", hit).with_clip("idle", SpriteClip::new(5..7, 1.));
+        let (indices, finished) = run(&mut controller, 4);
+        assert_eq!(indices, [1, 5, 6, 5]);
+        assert_eq!(finished, ["hit"]);
+        assert_eq!(controller.current(), "idle");
+        assert!(!controller.is_finished());
+    }
+
+    #[test]
+    fn missing_or_own_next_finishes_once() {
+        for next in ["missing", "hit"] {
+            let hit = SpriteClip::new(0..2, 1.)
+                .with_mode(LoopMode::HoldLast)
+                .with_next(next);
+            let mut controller = AnimationController::new("hit", hit);
+            let (indices, finished) = run(&mut controller, 5);
+            assert_eq!(indices, [1, 1, 1, 1, 1]);
+            assert_eq!(finished, ["hit"], "next {next:?}");
+            assert!(controller.is_finished());
+        }
+    }
+
+    #[test]
+    fn play_restarts_only_on_a_new_clip() {
+        let mut controller = AnimationController::new("walk", SpriteClip::new(0..4, 1.))
+            .with_clip("idle", SpriteClip::new(8..10, 1.));
+        controller.advance(1.);
+        assert!(controller.play("walk"));
+        assert_eq!(controller.atlas_index(), 1);
+        assert!(controller.play("idle"));
+        assert_eq!(controller.atlas_index(), 8);
+        assert!(!controller.play("missing"));
+        assert_eq!(controller.current(), "idle");
     }
 }
@@ -0,0 +1,290 @@
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use glam::UVec2;
+use maze_lite::maze::{
+    export::{self, ExportFormat},
+    generate, solve, MazeAlgorithm, MazeConfig, MazeGrid, MazeMetrics, MazeSeed,
+};
+
+const USAGE: &str = "\
+usage: maze-cli <command> [options]
+
+commands:
+    generate    generate mazes and print them
+    solve       generate mazes and print them with their solution
+    bench       generate and solve mazes, printing statistics per algorithm
+    export      generate a maze and write it out
+
+options:
+    --seed <seed>            seed of the first maze, random by default
+    --count <n>              number of mazes, seeds count up from --seed
+    --width <cells>          width in cells, including the solid edge
+    --height <cells>         height in cells, including the solid edge
+    --expansion <tiles>      tiles per cell
+    --algorithm <name>       random-walk, backtracker, prim, kruskal, wilson,
+                             eller, binary-tree, sidewinder or all
+    --braid <percent>        percentage of dead ends to open up into loops
+    --topology <name>        square, isometric or hex, square by default
+    --format <format>        ascii, png or json, ascii by default
+    --out <file>             file to export to, printed when left out (not for png)";
+
+/// Options shared by every command, anything not given keeps its default.
+struct Options {
+    config: MazeConfig,
+    seed: MazeSeed,
+    count: u32,
+    algorithms: Vec<MazeAlgorithm>,
+    format: ExportFormat,
+    out: Option<PathBuf>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            config: MazeConfig::default(),
+            seed: MazeSeed::random(),
+            count: 1,
+            algorithms: vec![MazeAlgorithm::default()],
+            format: ExportFormat::Ascii,
+            out: None,
+        };
+
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for {flag}"))?;
+            let number = || {
+                value
+                    .parse::<u32>()
+                    .map_err(|e| format!("invalid {flag} {value:?}: {e}"))
+            };
+
+            match flag.as_str() {
+                "--seed" => {
+                    options.seed = MazeSeed(
+                        value
+                            .parse()
+                            .map_err(|e| format!("invalid seed {value:?}: {e}"))?,
+                    )
+                }
+                "--count" => options.count = number()?,
+                "--width" => options.config.width = number()?,
+                "--height" => options.config.height = number()?,
+                "--expansion" => options.config.expansion = number()?,
+                "--braid" => options.config.braid = number()?,
+                "--topology" => options.config.topology = value.parse()?,
+                "--algorithm" if value == "all" => options.algorithms = MazeAlgorithm::ALL.into(),
+                "--algorithm" => options.algorithms = vec![value.parse()?],
+                "--format" => options.format = value.parse()?,
+                "--out" => options.out = Some(PathBuf::from(value)),
+                _ => return Err(format!("unknown option {flag}")),
+            }
+        }
+
+        if options.config.width < 3 || options.config.height < 3 {
+            return Err(String::from("mazes must be at least 3x3 cells"));
+        }
+        if options.config.braid > 100 {
+            return Err(String::from("braid is a percentage, at most 100"));
+        }
+        if options.config.expansion < 3 {
+            return Err(String::from("expansion must be at least 3"));
+        }
+
+        Ok(options)
+    }
+
+    /// Every maze to generate, `count` seeds for each algorithm.
+    fn mazes(&self) -> impl Iterator<Item = (MazeConfig, MazeSeed)> + '_ {
+        self.algorithms.iter().flat_map(move |algorithm| {
+            (0..self.count).map(move |i| {
+                let config = MazeConfig {
+                    algorithm: *algorithm,
+                    ..self.config.clone()
+                };
+                (config, MazeSeed(self.seed.0.wrapping_add(i as u64)))
+            })
+        })
+    }
+}
+
+/// Timings and metrics for a single generated maze.
+struct MazeStats {
+    generation: Duration,
+    solve: Duration,
+    metrics: MazeMetrics,
+}
+
+impl MazeStats {
+    fn measure(config: &MazeConfig, seed: MazeSeed) -> (MazeGrid, Vec<UVec2>, Self) {
+        let start = Instant::now();
+        let grid = generate(config, &mut seed.rng());
+        let generation = start.elapsed();
+
+        let start = Instant::now();
+        let path = solve::astar(&grid, grid.start(), grid.end()).unwrap_or_default();
+        let solve = start.elapsed();
+
+        // Metrics need one tile wide corridors, the layout is the same for any expansion.
+        let metrics = if config.expansion == 3 {
+            MazeMetrics::measure(&grid)
+        } else {
+            let config = MazeConfig {
+                expansion: 3,
+                ..config.clone()
+            };
+            MazeMetrics::measure(&generate(&config, &mut seed.rng()))
+        };
+
+        let stats = Self {
+            generation,
+            solve,
+            metrics,
+        };
+        (grid, path, stats)
+    }
+}
+
+fn generate_mazes(options: &Options) -> Result<(), String> {
+    for (config, seed) in options.mazes() {
+        println!("{} seed {seed}", config.algorithm);
+        print!(
+            "{}",
+            export::to_ascii(&generate(&config, &mut seed.rng()), &[])
+        );
+    }
+
+    Ok(())
+}
+
+fn solve_mazes(options: &Options) -> Result<(), String> {
+    for (config, seed) in options.mazes() {
+        let (grid, path, stats) = MazeStats::measure(&config, seed);
+        println!(
+            "{} seed {seed}: solution is {} tiles, solved in {:.3}ms, difficulty {:.2}",
+            config.algorithm,
+            path.len(),
+            stats.solve.as_secs_f64() * 1000.,
+            stats.metrics.difficulty(),
+        );
+        print!("{}", export::to_ascii(&grid, &path));
+    }
+
+    Ok(())
+}
+
+fn bench(options: &Options) -> Result<(), String> {
+    let size = options.config.map_size();
+    println!(
+        "{} mazes per algorithm, {}x{} cells, {}x{} tiles\n",
+        options.count, options.config.width, options.config.height, size.x, size.y
+    );
+    println!(
+        "{:<12} {:>8} {:>8} {:>8} {:>8} {:>8} {:>9} {:>9} {:>9} {:>8} {:>10}",
+        "algorithm",
+        "gen ms",
+        "max ms",
+        "solve ms",
+        "path",
+        "turns",
+        "dead ends",
+        "junctions",
+        "branching",
+        "river",
+        "difficulty"
+    );
+
+    for algorithm in options.algorithms.iter() {
+        let stats = options
+            .mazes()
+            .filter(|(config, _)| config.algorithm == *algorithm)
+            .map(|(config, seed)| MazeStats::measure(&config, seed).2)
+            .collect::<Vec<_>>();
+
+        let mean = |f: &dyn Fn(&MazeStats) -> f64| {
+            stats.iter().map(f).sum::<f64>() / stats.len().max(1) as f64
+        };
+        let max_generation = stats
+            .iter()
+            .map(|stats| stats.generation)
+            .max()
+            .unwrap_or_default();
+
+        println!(
+            "{:<12} {:>8.3} {:>8.3} {:>8.3} {:>8.1} {:>8.1} {:>9.1} {:>9.1} {:>9.2} {:>8.2} {:>10.2}",
+            algorithm.name(),
+            mean(&|stats| stats.generation.as_secs_f64() * 1000.),
+            max_generation.as_secs_f64() * 1000.,
+            mean(&|stats| stats.solve.as_secs_f64() * 1000.),
+            mean(&|stats| stats.metrics.solution_length as f64),
+            mean(&|stats| stats.metrics.turns as f64),
+            mean(&|stats| stats.metrics.dead_ends as f64),
+            mean(&|stats| stats.metrics.junctions as f64),
+            mean(&|stats| stats.metrics.branching as f64),
+            mean(&|stats| stats.metrics.river as f64),
+            mean(&|stats| stats.metrics.difficulty() as f64),
+        );
+    }
+
+    Ok(())
+}
+
+fn export(options: &Options) -> Result<(), String> {
+    let [algorithm] = options.algorithms[..] else {
+        return Err(String::from("export needs a single algorithm"));
+    };
+    let config = MazeConfig {
+        algorithm,
+        ..options.config.clone()
+    };
+    let grid = generate(&config, &mut options.seed.rng());
+
+    if let Some(out) = &options.out {
+        return export::export(&grid, options.format, out)
+            .map_err(|e| format!("failed to write {}: {e}", out.display()));
+    }
+
+    let path = solve::bfs(&grid, grid.start(), grid.end()).unwrap_or_default();
+    match options.format {
+        ExportFormat::Ascii => print!("{}", export::to_ascii(&grid, &path)),
+        ExportFormat::Json => println!("{}", export::to_json(&grid, &path)),
+        ExportFormat::Png => return Err(String::from("png needs an --out file")),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+
+    let command: fn(&Options) -> Result<(), String> = match args.next().as_deref() {
+        Some("generate") => generate_mazes,
+        Some("solve") => solve_mazes,
+        Some("bench") => bench,
+        Some("export") => export,
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(command) => {
+            eprintln!("unknown command {command}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match Options::parse(args).and_then(|options| command(&options)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
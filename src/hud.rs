@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::{
+    maze::MazeFloors, player::Hint, progression::LevelProgression, state::AppState, stats::RunStats,
+};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(AppState::Menu), spawn_hud)
+            .add_systems(OnEnter(AppState::Menu), despawn_hud)
+            .add_systems(Update, update_hud);
+    }
+}
+
+#[derive(Component)]
+struct HudText;
+
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        HudText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.,
+                ..Default::default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.),
+            top: Val::Px(8.),
+            ..Default::default()
+        }),
+    ));
+}
+
+fn despawn_hud(mut commands: Commands, huds: Query<Entity, With<HudText>>) {
+    for entity in huds.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update_hud(
+    stats: Res<RunStats>,
+    hint: Res<Hint>,
+    progression: Res<LevelProgression>,
+    floors: Option<Res<MazeFloors>>,
+    mut text: Query<&mut Text, With<HudText>>,
+) {
+    for mut text in text.iter_mut() {
+        let hint = if hint.cooldown_remaining().is_zero() {
+            String::from("ready")
+        } else {
+            format!("{:.0}s", hint.cooldown_remaining().as_secs_f32().ceil())
+        };
+
+        let floor = floors
+            .as_ref()
+            .filter(|floors| floors.len() > 1)
+            .map(|floors| format!("\nfloor: {}/{}", floors.current() + 1, floors.len()))
+            .unwrap_or_default();
+
+        text.sections[0].value = format!(
+            "level: {}{floor}\ntime: {:.1}s\ndistance: {:.0} tiles\nrestarts: {}\nhint: {hint}\nseed: {}",
+            progression.level + 1,
+            stats.total_time().as_secs_f32(),
+            stats.distance,
+            stats.restarts,
+            stats.seed.map(|seed| seed.to_string()).unwrap_or_default(),
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    cmp::Reverse,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    maze::{CustomMaze, MazeConfig, MazeSeed, Topology},
+    persist,
+    state::{spawn_screen, AppState},
+    stats::RunStats,
+};
+
+const LEADERBOARD_FILE: &str = "leaderboard.ron";
+/// How many runs are kept for every seed and configuration.
+const RUNS_PER_MAZE: usize = 10;
+/// How many configurations the leaderboard screen lists, most recently played first.
+const SCREEN_CONFIGS: usize = 3;
+/// How many runs the leaderboard screen lists for each configuration.
+const SCREEN_RUNS: usize = 3;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Leaderboard::load())
+            .add_systems(OnEnter(AppState::LevelComplete), record_run)
+            .add_systems(OnEnter(AppState::Leaderboard), spawn_leaderboard_screen);
+    }
+}
+
+/// Best runs for every seed and configuration that has been completed,
+/// stored in the platform data directory.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub mazes: Vec<MazeRecords>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MazeRecords {
+    pub seed: MazeSeed,
+    pub config: MazeConfig,
+    /// Fastest first.
+    pub runs: Vec<RunRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Level time including penalties.
+    pub time: Duration,
+    /// Distance walked in tiles.
+    pub distance: f32,
+    pub restarts: u32,
+    pub hints: u32,
+    /// Seconds since the unix epoch.
+    pub date: u64,
+}
+
+impl RunRecord {
+    pub fn new(stats: &RunStats) -> Self {
+        Self {
+            time: stats.total_time(),
+            distance: stats.distance,
+            restarts: stats.restarts,
+            hints: stats.hints,
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+}
+
+impl Leaderboard {
+    pub fn path() -> Option<PathBuf> {
+        persist::path(LEADERBOARD_FILE)
+    }
+
+    /// Loads the leaderboard, starting a new one if the file is missing or corrupt.
+    ///
+    /// A corrupt file is moved aside rather than overwritten.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            warn!("no data directory, the leaderboard will not be saved");
+            return Self::default();
+        };
+
+        Self::load_from(&path)
+    }
+
+    /// Loads the leaderboard at `path`, see [`Self::load`].
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                warn!("failed to read leaderboard {}: {e}", path.display());
+                return Self::default();
+            }
+        };
+
+        match ron::from_str(&contents) {
+            Ok(leaderboard) => leaderboard,
+            Err(e) => {
+                let backup = path.with_extension("ron.corrupt");
+                warn!(
+                    "corrupt leaderboard {}: {e}, moving it to {}",
+                    path.display(),
+                    backup.display()
+                );
+                if let Err(e) = fs::rename(path, &backup) {
+                    warn!("failed to move corrupt leaderboard: {e}");
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        // `load` has already warned when there is nowhere to save to.
+        if Self::path().is_none() {
+            return Ok(());
+        }
+
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+
+        persist::write_atomic(LEADERBOARD_FILE, contents)
+    }
+
+    pub fn runs(&self, seed: MazeSeed, config: &MazeConfig) -> &[RunRecord] {
+        self.mazes
+            .iter()
+            .find(|maze| maze.seed == seed && maze.config == *config)
+            .map(|maze| maze.runs.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Records a run, returning its place on the board for this maze starting from 0,
+    /// or `None` if it did not make the cut.
+    pub fn insert(&mut self, seed: MazeSeed, config: &MazeConfig, run: RunRecord) -> Option<usize> {
+        let maze = match self
+            .mazes
+            .iter_mut()
+            .position(|maze| maze.seed == seed && maze.config == *config)
+        {
+            Some(i) => &mut self.mazes[i],
+            None => {
+                self.mazes.push(MazeRecords {
+                    seed,
+                    config: config.clone(),
+                    runs: Vec::new(),
+                });
+                self.mazes.last_mut().unwrap()
+            }
+        };
+
+        let place = maze.runs.partition_point(|other| other.time <= run.time);
+        if place >= RUNS_PER_MAZE {
+            return None;
+        }
+
+        maze.runs.insert(place, run);
+        maze.runs.truncate(RUNS_PER_MAZE);
+        Some(place)
+    }
+
+    /// The fastest runs on any seed with `config`.
+    pub fn best(&self, config: &MazeConfig) -> Vec<(MazeSeed, &RunRecord)> {
+        let mut runs = self
+            .mazes
+            .iter()
+            .filter(|maze| maze.config == *config)
+            .flat_map(|maze| maze.runs.iter().map(|run| (maze.seed, run)))
+            .collect::<Vec<_>>();
+        runs.sort_by_key(|(_, run)| run.time);
+        runs
+    }
+
+    /// The fastest runs for every configuration, see [`Self::best`], with the most recently
+    /// played configuration first.
+    pub fn by_config(&self) -> Vec<(&MazeConfig, Vec<(MazeSeed, &RunRecord)>)> {
+        let mut configs: Vec<&MazeConfig> = Vec::new();
+        for maze in &self.mazes {
+            if !configs.contains(&&maze.config) {
+                configs.push(&maze.config);
+            }
+        }
+
+        let mut groups = configs
+            .into_iter()
+            .map(|config| (config, self.best(config)))
+            .collect::<Vec<_>>();
+        groups.sort_by_key(|(_, runs)| Reverse(runs.iter().map(|(_, run)| run.date).max()));
+        groups
+    }
+}
+
+fn record_run(
+    mut leaderboard: ResMut<Leaderboard>,
+    stats: Res<RunStats>,
+    seed: Res<MazeSeed>,
+    config: Res<MazeConfig>,
+    custom: Res<CustomMaze>,
+) {
+    // The seed and config don't describe a hand authored maze, so there is nothing to
+    // file the run under.
+    if custom.0.is_some() {
+        info!("runs on custom mazes are not recorded");
+        return;
+    }
+
+    if let Some(place) = leaderboard.insert(*seed, &config, RunRecord::new(&stats)) {
+        info!("new #{} time for seed {}", place + 1, *seed);
+        if let Err(e) = leaderboard.save() {
+            warn!("failed to save leaderboard: {e}");
+        }
+    }
+}
+
+/// Lists the best runs of the last few configurations played, the menu has no maze of its
+/// own to go by.
+fn spawn_leaderboard_screen(mut commands: Commands, leaderboard: Res<Leaderboard>) {
+    let groups = leaderboard.by_config();
+    let mut text = String::from("best times\n\n");
+
+    if groups.is_empty() {
+        text.push_str("no runs yet\n\n");
+    }
+
+    for (config, runs) in groups.iter().take(SCREEN_CONFIGS) {
+        text.push_str(&format!("{}\n", describe(config)));
+        for (i, (seed, run)) in runs.iter().take(SCREEN_RUNS).enumerate() {
+            text.push_str(&format!(
+                "{}. {:.2}s  {:.0} tiles  {}  seed {}\n",
+                i + 1,
+                run.time.as_secs_f32(),
+                run.distance,
+                format_date(run.date),
+                seed
+            ));
+        }
+        text.push('\n');
+    }
+
+    text.push_str("escape to go back");
+    spawn_screen(&mut commands, AppState::Leaderboard, text);
+}
+
+/// A short heading for the mazes generated with `config`, e.g. `16x16 prim, braid 10`.
+fn describe(config: &MazeConfig) -> String {
+    let mut text = format!("{}x{} {}", config.width, config.height, config.algorithm);
+    if config.braid > 0 {
+        text.push_str(&format!(", braid {}", config.braid));
+    }
+    if config.floors > 1 {
+        text.push_str(&format!(", {} floors", config.floors));
+    }
+    if config.topology != Topology::Square {
+        text.push_str(&format!(", {}", config.topology));
+    }
+    text
+}
+
+/// Formats seconds since the unix epoch as a `YYYY-MM-DD` date in UTC.
+fn format_date(secs: u64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A path in the temp directory that is unique to this test.
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("maze-lite-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn run(secs: u64) -> RunRecord {
+        RunRecord {
+            time: Duration::from_secs(secs),
+            distance: 10.,
+            restarts: 0,
+            hints: 0,
+            date: 0,
+        }
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_board() {
+        let leaderboard = Leaderboard::load_from(&temp_path("missing.ron"));
+        assert!(leaderboard.mazes.is_empty());
+    }
+
+    #[test]
+    fn corrupt_file_is_moved_aside() {
+        let path = temp_path("corrupt.ron");
+        fs::write(&path, "this is not a leaderboard").unwrap();
+
+        let leaderboard = Leaderboard::load_from(&path);
+        assert!(leaderboard.mazes.is_empty());
+        assert!(!path.exists());
+        assert!(path.with_extension("ron.corrupt").exists());
+    }
+
+    #[test]
+    fn insert_round_trips() {
+        let config = MazeConfig::default();
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.insert(MazeSeed(1), &config, run(30)), Some(0));
+        assert_eq!(leaderboard.insert(MazeSeed(1), &config, run(20)), Some(0));
+        assert_eq!(leaderboard.insert(MazeSeed(1), &config, run(25)), Some(1));
+        assert_eq!(leaderboard.insert(MazeSeed(2), &config, run(40)), Some(0));
+
+        let path = temp_path("round_trip.ron");
+        let contents =
+            ron::ser::to_string_pretty(&leaderboard, ron::ser::PrettyConfig::default()).unwrap();
+        fs::write(&path, contents).unwrap();
+
+        let loaded = Leaderboard::load_from(&path);
+        let times = |seed| {
+            loaded
+                .runs(MazeSeed(seed), &config)
+                .iter()
+                .map(|run| run.time.as_secs())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(times(1), [20, 25, 30]);
+        assert_eq!(times(2), [40]);
+    }
+
+    #[test]
+    fn only_the_fastest_runs_are_kept() {
+        let config = MazeConfig::default();
+        let mut leaderboard = Leaderboard::default();
+        for secs in 0..RUNS_PER_MAZE as u64 {
+            leaderboard.insert(MazeSeed(1), &config, run(secs));
+        }
+        assert_eq!(leaderboard.insert(MazeSeed(1), &config, run(100)), None);
+        assert_eq!(leaderboard.runs(MazeSeed(1), &config).len(), RUNS_PER_MAZE);
+    }
+
+    #[test]
+    fn groups_runs_by_config() {
+        let small = MazeConfig::default();
+        let large = MazeConfig {
+            width: 32,
+            height: 32,
+            ..Default::default()
+        };
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(MazeSeed(1), &small, RunRecord { date: 1, ..run(30) });
+        leaderboard.insert(MazeSeed(2), &large, RunRecord { date: 2, ..run(90) });
+        leaderboard.insert(MazeSeed(3), &small, RunRecord { date: 3, ..run(20) });
+
+        let groups = leaderboard.by_config();
+        let times = |runs: &[(MazeSeed, &RunRecord)]| {
+            runs.iter()
+                .map(|(seed, run)| (seed.0, run.time.as_secs()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(groups.len(), 2);
+        // The small maze was played last.
+        assert_eq!(*groups[0].0, small);
+        assert_eq!(times(&groups[0].1), [(3, 20), (1, 30)]);
+        assert_eq!(*groups[1].0, large);
+        assert_eq!(times(&groups[1].1), [(2, 90)]);
+    }
+}
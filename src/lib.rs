@@ -0,0 +1,21 @@
+#[cfg(feature = "game")]
+pub mod animated_sprites;
+#[cfg(feature = "game")]
+pub mod hud;
+#[cfg(feature = "game")]
+pub mod leaderboard;
+pub mod maze;
+#[cfg(feature = "game")]
+pub mod minimap;
+#[cfg(feature = "game")]
+pub mod persist;
+#[cfg(feature = "game")]
+pub mod player;
+#[cfg(feature = "game")]
+pub mod progression;
+#[cfg(feature = "game")]
+pub mod save;
+#[cfg(feature = "game")]
+pub mod state;
+#[cfg(feature = "game")]
+pub mod stats;
@@ -4,12 +4,11 @@ use bevy::{
     prelude::*,
     window::WindowResolution,
 };
-use maze::MazePlugin;
-use player::PlayerPlugin;
-
-pub mod animated_sprites;
-pub mod maze;
-pub mod player;
+use maze_lite::{
+    animated_sprites::AnimatedSpritePlugin, hud::HudPlugin, leaderboard::LeaderboardPlugin,
+    maze::MazePlugin, minimap::MinimapPlugin, player::PlayerPlugin, progression::ProgressionPlugin,
+    save::SavePlugin, state::StatePlugin, stats::StatsPlugin,
+};
 
 fn main() {
     App::default()
@@ -25,9 +24,17 @@ fn main() {
                 })
                 // for crisp sprites if we want that
                 .set(ImagePlugin::default_nearest()),
+            StatePlugin,
+            StatsPlugin,
             MazePlugin,
             PhysicsPlugins::default(),
             PlayerPlugin,
+            AnimatedSpritePlugin,
+            MinimapPlugin,
+            HudPlugin,
+            LeaderboardPlugin,
+            SavePlugin,
+            ProgressionPlugin,
             FrameTimeDiagnosticsPlugin,
             LogDiagnosticsPlugin::default(),
         ))
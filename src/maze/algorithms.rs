@@ -0,0 +1,115 @@
+use std::{fmt, str::FromStr};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::cells::CellGrid;
+
+mod backtracker;
+mod binary_tree;
+mod eller;
+mod kruskal;
+mod prim;
+mod random_walk;
+mod sidewinder;
+mod wilson;
+
+pub use backtracker::RecursiveBacktracker;
+pub use binary_tree::BinaryTree;
+pub use eller::Eller;
+pub use kruskal::Kruskal;
+pub use prim::Prim;
+pub use random_walk::RandomWalk;
+pub use sidewinder::Sidewinder;
+pub use wilson::Wilson;
+
+/// An algorithm that carves passages into a [`CellGrid`].
+pub trait MazeGenerator {
+    /// Carves passages between the cells of `cells`, which start out fully walled off.
+    ///
+    /// The start and end cells must end up connected, cells that are never
+    /// linked are left solid.
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MazeAlgorithm {
+    /// Loop erased random walk critical path with random walk branches.
+    /// Produces long winding corridors with few dead ends.
+    #[default]
+    RandomWalk,
+    /// Depth first search, produces long corridors with few, long dead ends.
+    RecursiveBacktracker,
+    /// Randomized Prim's, produces many short dead ends radiating from the start.
+    Prim,
+    /// Randomized Kruskal's, produces lots of short dead ends spread evenly.
+    Kruskal,
+    /// Loop erased random walks, produces an unbiased sample of all possible mazes.
+    Wilson,
+    /// Row by row set merging, similar to Kruskal's but only ever needs one row in memory.
+    Eller,
+    /// Links every cell up or right, produces a strong diagonal bias with open top and right edges.
+    BinaryTree,
+    /// Row by row runs carved upwards, produces an open top edge and vertical bias.
+    Sidewinder,
+}
+
+impl MazeAlgorithm {
+    pub const ALL: [MazeAlgorithm; 8] = [
+        Self::RandomWalk,
+        Self::RecursiveBacktracker,
+        Self::Prim,
+        Self::Kruskal,
+        Self::Wilson,
+        Self::Eller,
+        Self::BinaryTree,
+        Self::Sidewinder,
+    ];
+
+    pub fn generator(self) -> &'static dyn MazeGenerator {
+        match self {
+            Self::RandomWalk => &RandomWalk,
+            Self::RecursiveBacktracker => &RecursiveBacktracker,
+            Self::Prim => &Prim,
+            Self::Kruskal => &Kruskal,
+            Self::Wilson => &Wilson,
+            Self::Eller => &Eller,
+            Self::BinaryTree => &BinaryTree,
+            Self::Sidewinder => &Sidewinder,
+        }
+    }
+
+    /// Short name used on the command line and in exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::RandomWalk => "random-walk",
+            Self::RecursiveBacktracker => "backtracker",
+            Self::Prim => "prim",
+            Self::Kruskal => "kruskal",
+            Self::Wilson => "wilson",
+            Self::Eller => "eller",
+            Self::BinaryTree => "binary-tree",
+            Self::Sidewinder => "sidewinder",
+        }
+    }
+}
+
+impl fmt::Display for MazeAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for MazeAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown algorithm {s:?}, expected one of {names}")
+            })
+    }
+}
@@ -0,0 +1,34 @@
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::CellGrid;
+
+/// Depth first search that backtracks whenever it runs out of unvisited neighbors.
+pub struct RecursiveBacktracker;
+
+impl MazeGenerator for RecursiveBacktracker {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let mut visited = vec![false; cells.len()];
+        let start = rng.gen_range(0..cells.len());
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(&current) = stack.last() {
+            let unvisited = cells
+                .neighbors(current)
+                .filter(|(_, n)| !visited[*n])
+                .collect::<Vec<_>>();
+
+            match unvisited.choose(rng) {
+                Some(&(dir, next)) => {
+                    cells.link(current, dir);
+                    visited[next] = true;
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
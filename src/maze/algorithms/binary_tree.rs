@@ -0,0 +1,22 @@
+use rand::{seq::SliceRandom, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::{CellGrid, Direction};
+
+/// Links every cell either up or right.
+pub struct BinaryTree;
+
+impl MazeGenerator for BinaryTree {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        for cell in 0..cells.len() {
+            let options = [Direction::Up, Direction::Right]
+                .into_iter()
+                .filter(|dir| cells.neighbor(cell, *dir).is_some())
+                .collect::<Vec<_>>();
+
+            if let Some(dir) = options.choose(rng) {
+                cells.link(cell, *dir);
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::{CellGrid, Direction};
+
+/// Eller's algorithm, carves one row at a time by randomly merging neighboring sets
+/// and then linking every set at least once into the next row.
+pub struct Eller;
+
+impl MazeGenerator for Eller {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let width = cells.width() as usize;
+        let height = cells.height() as usize;
+
+        let mut next_set = 0;
+        let mut row_sets: Vec<Option<usize>> = vec![None; width];
+
+        for y in 0..height {
+            let row = y * width;
+            let last_row = y == height - 1;
+
+            for set in row_sets.iter_mut().filter(|set| set.is_none()) {
+                *set = Some(next_set);
+                next_set += 1;
+            }
+
+            // Randomly merge neighbors, the last row merges everything left.
+            for x in 0..width - 1 {
+                let (a, b) = (row_sets[x], row_sets[x + 1]);
+                if a != b && (last_row || rng.gen_bool(0.5)) {
+                    cells.link(row + x, Direction::Right);
+                    for set in row_sets.iter_mut().filter(|set| **set == b) {
+                        *set = a;
+                    }
+                }
+            }
+
+            if last_row {
+                break;
+            }
+
+            // Every set needs at least one passage into the next row.
+            let mut next_row_sets = vec![None; width];
+            let mut members = (0..width).collect::<Vec<_>>();
+            members.shuffle(rng);
+            let mut linked_sets = Vec::new();
+            for x in members {
+                let set = row_sets[x];
+                if !linked_sets.contains(&set) || rng.gen_bool(0.3) {
+                    cells.link(row + x, Direction::Up);
+                    next_row_sets[x] = set;
+                    linked_sets.push(set);
+                }
+            }
+
+            row_sets = next_row_sets;
+        }
+    }
+}
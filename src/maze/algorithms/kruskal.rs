@@ -0,0 +1,62 @@
+use rand::{seq::SliceRandom, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::{CellGrid, Direction};
+
+/// Randomized Kruskal's, links walls in random order whenever they separate two
+/// cells that are not yet connected.
+pub struct Kruskal;
+
+impl MazeGenerator for Kruskal {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let mut edges = (0..cells.len())
+            .flat_map(|cell| {
+                // One direction from each opposite pair, so every wall is listed once.
+                [Direction::Up, Direction::Right, Direction::UpLeft]
+                    .into_iter()
+                    .map(move |dir| (cell, dir))
+            })
+            .filter_map(|(cell, dir)| cells.neighbor(cell, dir).map(|n| (cell, dir, n)))
+            .collect::<Vec<_>>();
+        edges.shuffle(rng);
+
+        let mut sets = DisjointSet::new(cells.len());
+        for (cell, dir, neighbor) in edges {
+            if sets.union(cell, neighbor) {
+                cells.link(cell, dir);
+            }
+        }
+    }
+}
+
+pub(crate) struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Joins the sets containing `a` and `b`, returns false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        self.parents[a] = b;
+        true
+    }
+}
@@ -0,0 +1,45 @@
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::CellGrid;
+
+/// Randomized Prim's, grows the maze outwards from a random cell by picking random
+/// frontier cells and linking them to a random neighbor already in the maze.
+pub struct Prim;
+
+impl MazeGenerator for Prim {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let mut in_maze = vec![false; cells.len()];
+        let mut in_frontier = vec![false; cells.len()];
+        let mut frontier = Vec::new();
+
+        let start = rng.gen_range(0..cells.len());
+        in_maze[start] = true;
+        for (_, n) in cells.neighbors(start) {
+            in_frontier[n] = true;
+            frontier.push(n);
+        }
+
+        while !frontier.is_empty() {
+            let current = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            in_maze[current] = true;
+
+            let neighbors = cells.neighbors(current).collect::<Vec<_>>();
+            if let Some(&(dir, _)) = neighbors
+                .iter()
+                .filter(|(_, n)| in_maze[*n])
+                .collect::<Vec<_>>()
+                .choose(rng)
+            {
+                cells.link(current, *dir);
+            }
+
+            for (_, n) in neighbors {
+                if !in_maze[n] && !in_frontier[n] {
+                    in_frontier[n] = true;
+                    frontier.push(n);
+                }
+            }
+        }
+    }
+}
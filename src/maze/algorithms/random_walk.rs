@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::CellGrid;
+
+/// Steps per cell the critical path walk may take before heading straight for the end.
+const CRITICAL_PATH_STEPS: usize = 32;
+/// Steps per cell shared by every branch walk, once used up the remaining cells are joined
+/// on directly.
+const BRANCH_STEPS: usize = 16;
+
+/// Walks a critical path from the start to the end, then fills the rest of the map
+/// with random walk branches that join back into it.
+///
+/// Every walk has a step budget proportional to the size of the grid, so generation
+/// always finishes in linear time no matter how unlucky the walks are.
+pub struct RandomWalk;
+
+impl MazeGenerator for RandomWalk {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let mut in_maze = vec![false; cells.len()];
+        let mut walk = Walk::new(cells.len());
+
+        let critical_path = walk
+            .run(
+                cells,
+                cells.start(),
+                |cell| cell == cells.end(),
+                cells.len() * CRITICAL_PATH_STEPS,
+                rng,
+            )
+            .unwrap_or_else(|| walk.finish(cells, cells.end()));
+        cells.link_path(&critical_path);
+        for cell in critical_path {
+            in_maze[cell] = true;
+        }
+
+        let mut starts = (0..cells.len()).collect::<Vec<_>>();
+        starts.shuffle(rng);
+
+        let mut budget = cells.len() * BRANCH_STEPS;
+        for start in starts {
+            if in_maze[start] {
+                continue;
+            }
+
+            let Some(branch) = walk.run(cells, start, |cell| in_maze[cell], budget, rng) else {
+                break;
+            };
+            budget -= walk.steps;
+
+            cells.link_path(&branch);
+            for cell in branch {
+                in_maze[cell] = true;
+            }
+        }
+
+        join_remaining(cells, &mut in_maze, rng);
+    }
+}
+
+/// A loop erased random walk, reused between walks to avoid reallocating.
+struct Walk {
+    path: Vec<usize>,
+    /// Index of each cell in `path`, used to erase loops without searching the path.
+    index: Vec<Option<usize>>,
+    /// Steps taken by the last walk.
+    steps: usize,
+}
+
+impl Walk {
+    fn new(len: usize) -> Self {
+        Self {
+            path: Vec::new(),
+            index: vec![None; len],
+            steps: 0,
+        }
+    }
+
+    /// Walks from `start` until reaching a cell where `done` is true, returning the path
+    /// including that cell, or `None` if it takes more than `budget` steps.
+    fn run(
+        &mut self,
+        cells: &CellGrid,
+        start: usize,
+        done: impl Fn(usize) -> bool,
+        budget: usize,
+        rng: &mut dyn RngCore,
+    ) -> Option<Vec<usize>> {
+        for cell in self.path.drain(..) {
+            self.index[cell] = None;
+        }
+        self.path.push(start);
+        self.index[start] = Some(0);
+        self.steps = 0;
+
+        let mut current = start;
+        while !done(current) {
+            if self.steps == budget {
+                return None;
+            }
+            self.steps += 1;
+
+            let directions = cells.directions();
+            let dir = directions[rng.gen_range(0..directions.len())];
+            let Some(next) = cells.neighbor(current, dir) else {
+                continue;
+            };
+
+            if let Some(i) = self.index[next] {
+                for erased in self.path.drain(i + 1..) {
+                    self.index[erased] = None;
+                }
+            } else {
+                self.index[next] = Some(self.path.len());
+                self.path.push(next);
+            }
+            current = next;
+        }
+
+        Some(self.path.clone())
+    }
+
+    /// Finishes a walk that ran out of steps by joining the shortest route from anywhere
+    /// on it to `end` that doesn't cross it.
+    fn finish(&mut self, cells: &CellGrid, end: usize) -> Vec<usize> {
+        let mut parent = vec![None; cells.len()];
+        let mut queue = self.path.iter().copied().collect::<VecDeque<_>>();
+
+        while let Some(cell) = queue.pop_front() {
+            if cell == end {
+                break;
+            }
+
+            for (_, neighbor) in cells.neighbors(cell) {
+                if self.index[neighbor].is_none() && parent[neighbor].is_none() {
+                    parent[neighbor] = Some(cell);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // The grid is connected, so some cell of the walk always reaches the end.
+        let mut route = vec![end];
+        let mut current = end;
+        while self.index[current].is_none() {
+            current = parent[current].expect("end is reachable from the walk");
+            route.push(current);
+        }
+
+        let joined = self.index[current].unwrap();
+        let mut path = self.path[..joined].to_vec();
+        path.extend(route.into_iter().rev());
+        path
+    }
+}
+
+/// Joins every cell that is not yet part of the maze onto a random neighbor that is,
+/// spreading outwards from the maze.
+fn join_remaining(cells: &mut CellGrid, in_maze: &mut [bool], rng: &mut dyn RngCore) {
+    let mut frontier = (0..cells.len())
+        .filter(|cell| in_maze[*cell])
+        .collect::<Vec<_>>();
+    frontier.shuffle(rng);
+    let mut frontier = VecDeque::from(frontier);
+
+    while let Some(cell) = frontier.pop_front() {
+        let mut neighbors = cells.neighbors(cell).collect::<Vec<_>>();
+        neighbors.shuffle(rng);
+
+        for (dir, neighbor) in neighbors {
+            if !in_maze[neighbor] {
+                cells.link(cell, dir);
+                in_maze[neighbor] = true;
+                frontier.push_back(neighbor);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use rand::{Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::{CellGrid, Direction};
+
+/// Carves runs of cells to the right along each row, closing each run with a
+/// single passage up from a random cell in it. The top row is one long corridor.
+pub struct Sidewinder;
+
+impl MazeGenerator for Sidewinder {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let width = cells.width() as usize;
+        let height = cells.height() as usize;
+
+        for y in 0..height {
+            let mut run_start = 0;
+
+            for x in 0..width {
+                let cell = y * width + x;
+                let at_right = x == width - 1;
+                let at_top = y == height - 1;
+
+                if at_right || (!at_top && rng.gen_bool(0.5)) {
+                    if !at_top {
+                        let up = y * width + rng.gen_range(run_start..=x);
+                        cells.link(up, Direction::Up);
+                    }
+                    run_start = x + 1;
+                } else {
+                    cells.link(cell, Direction::Right);
+                }
+            }
+        }
+    }
+}
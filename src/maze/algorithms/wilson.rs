@@ -0,0 +1,56 @@
+use rand::{seq::SliceRandom, Rng, RngCore};
+
+use super::MazeGenerator;
+use crate::maze::cells::CellGrid;
+
+/// Wilson's algorithm, repeatedly performs loop erased random walks from unvisited
+/// cells until they hit the maze. Samples uniformly from every possible maze.
+pub struct Wilson;
+
+impl MazeGenerator for Wilson {
+    fn carve(&self, cells: &mut CellGrid, rng: &mut dyn RngCore) {
+        let mut in_maze = vec![false; cells.len()];
+        in_maze[rng.gen_range(0..cells.len())] = true;
+
+        let mut unvisited = (0..cells.len())
+            .filter(|c| !in_maze[*c])
+            .collect::<Vec<_>>();
+        unvisited.shuffle(rng);
+
+        // Index of each cell in the current walk, used to erase loops.
+        let mut walk_index = vec![None; cells.len()];
+        for start in unvisited {
+            if in_maze[start] {
+                continue;
+            }
+
+            let mut walk = vec![start];
+            walk_index[start] = Some(0);
+            let mut current = start;
+
+            while !in_maze[current] {
+                let directions = cells.directions();
+                let dir = directions[rng.gen_range(0..directions.len())];
+                let Some(next) = cells.neighbor(current, dir) else {
+                    continue;
+                };
+
+                if let Some(i) = walk_index[next] {
+                    for erased in walk.drain(i + 1..) {
+                        walk_index[erased] = None;
+                    }
+                } else {
+                    walk_index[next] = Some(walk.len());
+                    walk.push(next);
+                }
+                current = next;
+            }
+
+            cells.link_path(&walk);
+            for cell in walk {
+                in_maze[cell] = true;
+                walk_index[cell] = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use rand::{seq::SliceRandom, RngCore};
+
+use super::cells::CellGrid;
+
+/// Removes `percent` of the dead ends of a carved maze by linking them to another
+/// neighbor, turning the tree of passages into one with loops.
+///
+/// Dead ends are opened towards other dead ends where possible so one link removes
+/// both, and only ever into cells that are already part of the maze. Both count towards
+/// `percent`, so about `100 - percent` of the dead ends are left.
+pub fn braid(cells: &mut CellGrid, percent: u32, rng: &mut dyn RngCore) {
+    if percent == 0 {
+        return;
+    }
+
+    let mut dead_ends = (0..cells.len())
+        .filter(|cell| is_dead_end(cells, *cell))
+        .collect::<Vec<_>>();
+    dead_ends.shuffle(rng);
+
+    let target = (dead_ends.len() * percent.min(100) as usize).div_ceil(100);
+    let mut cleared = 0;
+    for cell in dead_ends {
+        if cleared >= target {
+            break;
+        }
+        // An earlier link may have already opened this one up.
+        if !is_dead_end(cells, cell) {
+            continue;
+        }
+
+        let mut options = cells
+            .neighbors(cell)
+            .filter(|(dir, neighbor)| !cells.is_linked(cell, *dir) && cells.is_visited(*neighbor))
+            .collect::<Vec<_>>();
+        options.shuffle(rng);
+
+        // With one dead end left to clear, opening into another would clear one too many.
+        let pair = cleared + 1 < target;
+        let best = options
+            .iter()
+            .find(|(_, neighbor)| is_dead_end(cells, *neighbor) == pair)
+            .or(options.first());
+        if let Some((dir, neighbor)) = best {
+            cleared += if is_dead_end(cells, *neighbor) { 2 } else { 1 };
+            cells.link(cell, *dir);
+        }
+    }
+}
+
+fn is_dead_end(cells: &CellGrid, cell: usize) -> bool {
+    cells.links(cell).count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{MazeAlgorithm, MazeSeed, Topology};
+
+    fn dead_ends(cells: &CellGrid) -> usize {
+        (0..cells.len())
+            .filter(|cell| is_dead_end(cells, *cell))
+            .count()
+    }
+
+    #[test]
+    fn leaves_the_rest_of_the_dead_ends() {
+        for topology in Topology::ALL {
+            for algorithm in MazeAlgorithm::ALL {
+                let mut rng = MazeSeed(3).rng();
+                let mut cells = CellGrid::with_topology(20, 20, topology);
+                algorithm.generator().carve(&mut cells, &mut rng);
+                let before = dead_ends(&cells);
+
+                for percent in [0, 10, 25, 50, 75, 100] {
+                    let mut braided = cells.clone();
+                    braid(&mut braided, percent, &mut rng);
+                    let expected = before - (before * percent as usize).div_ceil(100);
+                    let left = dead_ends(&braided);
+                    // The last link can open into a second dead end when there is no other way.
+                    assert!(
+                        (expected.saturating_sub(1)..=expected).contains(&left),
+                        "{topology} {algorithm} {percent}% left {left} of {before} dead ends, \
+                        expected {expected}"
+                    );
+                }
+            }
+        }
+    }
+}
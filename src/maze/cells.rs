@@ -0,0 +1,188 @@
+use glam::{IVec2, UVec2};
+
+use super::topology::Topology;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+    /// Only a neighbor in [`Topology::Hex`] grids.
+    UpLeft,
+    /// Only a neighbor in [`Topology::Hex`] grids.
+    DownRight,
+}
+
+impl Direction {
+    pub const SQUARE: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+    pub const HEX: [Direction; 6] = [
+        Self::Up,
+        Self::Right,
+        Self::Down,
+        Self::Left,
+        Self::UpLeft,
+        Self::DownRight,
+    ];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::UpLeft => Self::DownRight,
+            Self::DownRight => Self::UpLeft,
+        }
+    }
+
+    pub fn offset(self) -> IVec2 {
+        match self {
+            Self::Up => IVec2::Y,
+            Self::Right => IVec2::X,
+            Self::Down => IVec2::NEG_Y,
+            Self::Left => IVec2::NEG_X,
+            Self::UpLeft => IVec2::new(-1, 1),
+            Self::DownRight => IVec2::new(1, -1),
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The logical cells of a maze and the passages carved between them.
+///
+/// Cells are indexed row by row starting from the bottom left. Every cell starts out
+/// walled off from its neighbors, generators then link cells together to carve passages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellGrid {
+    width: u32,
+    height: u32,
+    topology: Topology,
+    links: Vec<u8>,
+}
+
+impl CellGrid {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_topology(width, height, Topology::Square)
+    }
+
+    pub fn with_topology(width: u32, height: u32, topology: Topology) -> Self {
+        Self {
+            width,
+            height,
+            topology,
+            links: vec![0; (width * height) as usize],
+        }
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Every direction a cell can have a neighbor in.
+    pub fn directions(&self) -> &'static [Direction] {
+        match self.topology {
+            Topology::Square | Topology::Isometric => &Direction::SQUARE,
+            Topology::Hex => &Direction::HEX,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of cells in the grid.
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// The bottom left cell, where the player starts.
+    pub fn start(&self) -> usize {
+        0
+    }
+
+    /// The top right cell, where the maze exits.
+    pub fn end(&self) -> usize {
+        self.len() - 1
+    }
+
+    pub fn index(&self, pos: UVec2) -> usize {
+        (pos.y * self.width + pos.x) as usize
+    }
+
+    pub fn pos(&self, cell: usize) -> UVec2 {
+        UVec2::new(cell as u32 % self.width, cell as u32 / self.width)
+    }
+
+    /// The cell next to `cell` in `dir`, if it is inside the grid and `dir` is one of
+    /// [`Self::directions`].
+    pub fn neighbor(&self, cell: usize, dir: Direction) -> Option<usize> {
+        if !self.directions().contains(&dir) {
+            return None;
+        }
+
+        let pos = self.pos(cell).as_ivec2() + dir.offset();
+        (pos.x >= 0 && pos.y >= 0 && (pos.x as u32) < self.width && (pos.y as u32) < self.height)
+            .then(|| self.index(pos.as_uvec2()))
+    }
+
+    pub fn neighbors(&self, cell: usize) -> impl Iterator<Item = (Direction, usize)> + '_ {
+        self.directions()
+            .iter()
+            .copied()
+            .filter_map(move |dir| self.neighbor(cell, dir).map(|n| (dir, n)))
+    }
+
+    /// Carves a passage from `cell` to its neighbor in `dir`.
+    pub fn link(&mut self, cell: usize, dir: Direction) {
+        let Some(neighbor) = self.neighbor(cell, dir) else {
+            return;
+        };
+
+        self.links[cell] |= dir.bit();
+        self.links[neighbor] |= dir.opposite().bit();
+    }
+
+    /// Carves a passage between two adjacent cells, does nothing if they are not adjacent.
+    pub fn link_cells(&mut self, a: usize, b: usize) {
+        let dir = self.neighbors(a).find(|(_, n)| *n == b).map(|(dir, _)| dir);
+        if let Some(dir) = dir {
+            self.link(a, dir);
+        }
+    }
+
+    /// Carves passages between each consecutive pair of cells in `path`.
+    pub fn link_path(&mut self, path: &[usize]) {
+        for pair in path.windows(2) {
+            self.link_cells(pair[0], pair[1]);
+        }
+    }
+
+    pub fn is_linked(&self, cell: usize, dir: Direction) -> bool {
+        self.links[cell] & dir.bit() != 0
+    }
+
+    /// Every direction `cell` has a passage in.
+    pub fn links(&self, cell: usize) -> impl Iterator<Item = Direction> + '_ {
+        self.directions()
+            .iter()
+            .copied()
+            .filter(move |dir| self.is_linked(cell, *dir))
+    }
+
+    /// Whether any passage has been carved into `cell`.
+    pub fn is_visited(&self, cell: usize) -> bool {
+        self.links[cell] != 0
+    }
+}
@@ -0,0 +1,202 @@
+use avian2d::prelude::*;
+use bevy::math::{URect, UVec2, Vec2};
+
+use super::{grid::MazeGrid, topology::Topology};
+
+/// Colliders covering every wall of `grid` the player can reach, each with the world
+/// position it goes at.
+pub fn wall_colliders(grid: &MazeGrid, tile_size: f32) -> Vec<(Vec2, Collider)> {
+    let topology = grid.topology();
+    // Tile positions map to world positions linearly, so offsetting by the first tile
+    // places fractional positions as well.
+    let origin = grid.tile_to_world(UVec2::ZERO, tile_size);
+
+    match topology {
+        Topology::Square => wall_rects(grid)
+            .into_iter()
+            .map(|rect| {
+                let size = rect.size().as_vec2() * tile_size;
+                let center = grid.tile_to_world(rect.min, tile_size) + (size - tile_size) * 0.5;
+                (center, Collider::rectangle(size.x, size.y))
+            })
+            .collect(),
+        // A rectangle of diamonds is a parallelogram.
+        Topology::Isometric => wall_rects(grid)
+            .into_iter()
+            .filter_map(|rect| {
+                let (min, max) = (rect.min.as_vec2() - 0.5, rect.max.as_vec2() - 0.5);
+                let center = topology.project((min + max) * 0.5, tile_size);
+                let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+                    .map(|corner| topology.project(corner, tile_size) - center);
+
+                Collider::convex_hull(corners.to_vec()).map(|collider| (origin + center, collider))
+            })
+            .collect(),
+        // Hexagons don't merge into larger convex shapes, so each stretch of walls lining
+        // the corridors becomes a single body made up of their hexagons.
+        Topology::Hex => {
+            let Some(hexagon) = Collider::convex_hull(topology.outline(tile_size)) else {
+                return Vec::new();
+            };
+
+            lining_walls(grid)
+                .into_iter()
+                .map(|region| {
+                    let center = grid.tile_to_world(region[0], tile_size);
+                    let hexagons = region
+                        .into_iter()
+                        .map(|pos| {
+                            let offset = grid.tile_to_world(pos, tile_size) - center;
+                            (Position(offset), Rotation::default(), hexagon.clone())
+                        })
+                        .collect();
+                    (center, Collider::compound(hexagons))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Groups the walls of `grid` with a floor next to them into regions of neighboring walls.
+/// The walls behind them can never be reached, so they are left out.
+fn lining_walls(grid: &MazeGrid) -> Vec<Vec<UVec2>> {
+    let is_lining = |pos: UVec2| grid.is_wall(pos) && grid.floor_neighbors(pos).next().is_some();
+    let mut seen = vec![false; grid.tiles().len()];
+    let mut regions = Vec::new();
+
+    for (start, _) in grid.iter() {
+        if seen[grid.index(start)] || !is_lining(start) {
+            continue;
+        }
+        seen[grid.index(start)] = true;
+
+        let mut region = Vec::new();
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            region.push(pos);
+            for neighbor in grid.neighbors(pos) {
+                if !seen[grid.index(neighbor)] && is_lining(neighbor) {
+                    seen[grid.index(neighbor)] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        regions.push(region);
+    }
+
+    regions
+}
+
+/// Greedily merges the wall tiles of `grid` into as few rectangles as it can,
+/// so each rectangle can get a single collider instead of one per tile.
+///
+/// Rectangles never overlap and cover exactly the wall tiles, `max` is exclusive.
+pub fn wall_rects(grid: &MazeGrid) -> Vec<URect> {
+    let mut covered = vec![false; grid.tiles().len()];
+    let mut rects = Vec::new();
+
+    let is_free = |covered: &[bool], pos: UVec2| grid.is_wall(pos) && !covered[grid.index(pos)];
+
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let min = UVec2::new(x, y);
+            if !is_free(&covered, min) {
+                continue;
+            }
+
+            let mut max = min + 1;
+            while max.x < grid.width() && is_free(&covered, UVec2::new(max.x, y)) {
+                max.x += 1;
+            }
+
+            while max.y < grid.height()
+                && (min.x..max.x).all(|x| is_free(&covered, UVec2::new(x, max.y)))
+            {
+                max.y += 1;
+            }
+
+            for y in min.y..max.y {
+                for x in min.x..max.x {
+                    covered[grid.index(UVec2::new(x, y))] = true;
+                }
+            }
+
+            rects.push(URect::from_corners(min, max));
+        }
+    }
+
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{generate, MazeAlgorithm, MazeConfig, MazeSeed};
+
+    fn maze(algorithm: MazeAlgorithm, topology: Topology) -> MazeGrid {
+        let config = MazeConfig {
+            width: 32,
+            height: 32,
+            algorithm,
+            topology,
+            ..Default::default()
+        };
+        generate(&config, &mut MazeSeed(7).rng())
+    }
+
+    #[test]
+    fn rects_cover_exactly_the_walls() {
+        for algorithm in MazeAlgorithm::ALL {
+            let grid = maze(algorithm, Topology::Square);
+            let mut covered = vec![0; grid.tiles().len()];
+            for rect in wall_rects(&grid) {
+                for y in rect.min.y..rect.max.y {
+                    for x in rect.min.x..rect.max.x {
+                        covered[grid.index(UVec2::new(x, y))] += 1;
+                    }
+                }
+            }
+
+            for (pos, _) in grid.iter() {
+                let expected = u32::from(grid.is_wall(pos));
+                assert_eq!(covered[grid.index(pos)], expected, "{algorithm} at {pos}");
+            }
+        }
+    }
+
+    #[test]
+    fn hex_regions_cover_exactly_the_lining_walls() {
+        for algorithm in MazeAlgorithm::ALL {
+            let grid = maze(algorithm, Topology::Hex);
+            let mut covered = vec![0; grid.tiles().len()];
+            for pos in lining_walls(&grid).into_iter().flatten() {
+                covered[grid.index(pos)] += 1;
+            }
+
+            for (pos, _) in grid.iter() {
+                let lining = grid.is_wall(pos) && grid.floor_neighbors(pos).next().is_some();
+                assert_eq!(
+                    covered[grid.index(pos)],
+                    u32::from(lining),
+                    "{algorithm} at {pos}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn colliders_are_far_fewer_than_walls() {
+        for topology in Topology::ALL {
+            for algorithm in MazeAlgorithm::ALL {
+                let grid = maze(algorithm, topology);
+                let walls = grid.iter().filter(|(pos, _)| grid.is_wall(*pos)).count();
+                let bodies = wall_colliders(&grid, 16.).len();
+                assert!(
+                    bodies * 10 <= walls,
+                    "{topology} {algorithm} merged {walls} walls into {bodies} bodies"
+                );
+            }
+        }
+    }
+}
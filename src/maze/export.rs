@@ -0,0 +1,253 @@
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+#[cfg(feature = "game")]
+use bevy::prelude::*;
+use glam::UVec2;
+use image::{ImageFormat, Rgba, RgbaImage};
+use serde::Serialize;
+
+use super::{solve, MazeGrid, TileType, Topology};
+#[cfg(feature = "game")]
+use super::{CurrentMaze, MazeSeed};
+#[cfg(feature = "game")]
+use crate::{persist, state::AppState};
+
+/// Pixels per tile in exported images.
+const IMAGE_SCALE: u32 = 4;
+
+const WALL_COLOR: Rgba<u8> = Rgba([0, 0, 0, 255]);
+const FLOOR_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+/// Light enough that the image still imports with the path as floor, see [`super::import`].
+const PATH_COLOR: Rgba<u8> = Rgba([100, 150, 255, 255]);
+const START_COLOR: Rgba<u8> = Rgba([0, 255, 0, 255]);
+const END_COLOR: Rgba<u8> = Rgba([255, 0, 0, 255]);
+
+#[cfg(feature = "game")]
+pub struct ExportPlugin;
+
+#[cfg(feature = "game")]
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            export_current_maze
+                .run_if(in_state(AppState::Playing).or_else(in_state(AppState::Paused))),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The same text format mazes are imported from, with the critical path drawn as `o`.
+    Ascii,
+    /// [`IMAGE_SCALE`] pixels per tile, with the critical path highlighted.
+    Png,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [Self::Ascii, Self::Png, Self::Json];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ascii => "maze",
+            Self::Png => "png",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ascii => "ascii",
+            Self::Png => "png",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown format {s:?}, expected one of {names}")
+            })
+    }
+}
+
+fn glyph(tile: TileType) -> char {
+    match tile {
+        TileType::Wall => '#',
+        TileType::Floor => '.',
+    }
+}
+
+/// Draws `grid` as text, top row first, marking the tiles of `path` with `o`.
+pub fn to_ascii(grid: &MazeGrid, path: &[UVec2]) -> String {
+    let mut glyphs = grid
+        .tiles()
+        .iter()
+        .map(|tile| glyph(*tile))
+        .collect::<Vec<_>>();
+
+    for pos in path {
+        glyphs[grid.index(*pos)] = 'o';
+    }
+    glyphs[grid.index(grid.start())] = 'S';
+    glyphs[grid.index(grid.end())] = 'E';
+
+    let mut text = String::with_capacity(glyphs.len() + grid.height() as usize);
+    for row in glyphs.chunks(grid.width() as usize).rev() {
+        text.extend(row);
+        text.push('\n');
+    }
+    text
+}
+
+/// Draws `grid` with `scale` x `scale` pixels per tile, highlighting the tiles of `path`.
+pub fn to_image(grid: &MazeGrid, path: &[UVec2], scale: u32) -> RgbaImage {
+    let mut colors = grid
+        .tiles()
+        .iter()
+        .map(|tile| {
+            if *tile == TileType::Wall {
+                WALL_COLOR
+            } else {
+                FLOOR_COLOR
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for pos in path {
+        colors[grid.index(*pos)] = PATH_COLOR;
+    }
+    colors[grid.index(grid.start())] = START_COLOR;
+    colors[grid.index(grid.end())] = END_COLOR;
+
+    RgbaImage::from_fn(grid.width() * scale, grid.height() * scale, |x, y| {
+        let pos = UVec2::new(x / scale, grid.height() - 1 - y / scale);
+        colors[grid.index(pos)]
+    })
+}
+
+/// A maze as written to JSON, positions are `[x, y]` from the bottom left.
+#[derive(Serialize)]
+struct MazeJson {
+    topology: Topology,
+    width: u32,
+    height: u32,
+    start: [u32; 2],
+    end: [u32; 2],
+    /// Rows of `#` and `.`, top row first.
+    rows: Vec<String>,
+    path: Vec<[u32; 2]>,
+}
+
+pub fn to_json(grid: &MazeGrid, path: &[UVec2]) -> String {
+    let rows = grid
+        .tiles()
+        .chunks(grid.width() as usize)
+        .rev()
+        .map(|row| row.iter().map(|tile| glyph(*tile)).collect())
+        .collect();
+
+    let json = MazeJson {
+        topology: grid.topology(),
+        width: grid.width(),
+        height: grid.height(),
+        start: grid.start().to_array(),
+        end: grid.end().to_array(),
+        rows,
+        path: path.iter().map(|pos| pos.to_array()).collect(),
+    };
+
+    serde_json::to_string_pretty(&json).expect("maze json is always serializable")
+}
+
+/// Writes `grid` to `file` in `format`, with its critical path.
+pub fn export(grid: &MazeGrid, format: ExportFormat, file: &Path) -> io::Result<()> {
+    let path = solve::bfs(grid, grid.start(), grid.end()).unwrap_or_default();
+
+    match format {
+        ExportFormat::Ascii => fs::write(file, to_ascii(grid, &path)),
+        ExportFormat::Png => to_image(grid, &path, IMAGE_SCALE)
+            .save_with_format(file, ImageFormat::Png)
+            .map_err(io::Error::other),
+        ExportFormat::Json => fs::write(file, to_json(grid, &path)),
+    }
+}
+
+/// Dumps the current maze in every format to the data directory.
+#[cfg(feature = "game")]
+fn export_current_maze(
+    input: Res<ButtonInput<KeyCode>>,
+    maze: Res<CurrentMaze>,
+    seed: Res<MazeSeed>,
+) {
+    if !input.just_pressed(KeyCode::F2) {
+        return;
+    }
+
+    let Some(dir) = persist::path("exports") else {
+        warn!("no data directory to export to");
+        return;
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("failed to create {}: {e}", dir.display());
+        return;
+    }
+
+    for format in ExportFormat::ALL {
+        let file = dir.join(format!("maze-{}.{}", *seed, format.extension()));
+        match export(&maze, format, &file) {
+            Ok(()) => info!("exported maze to {}", file.display()),
+            Err(e) => warn!("failed to export maze to {}: {e}", file.display()),
+        }
+    }
+}
+
+// Importing lives with the game's asset loaders.
+#[cfg(all(test, feature = "game"))]
+mod tests {
+    use super::*;
+    use crate::maze::{generate, import, MazeAlgorithm, MazeConfig, MazeSeed};
+
+    #[test]
+    fn ascii_imports_back_as_the_same_maze() {
+        for algorithm in MazeAlgorithm::ALL {
+            let config = MazeConfig {
+                algorithm,
+                braid: 20,
+                ..Default::default()
+            };
+            let grid = generate(&config, &mut MazeSeed(9).rng());
+            let path = solve::bfs(&grid, grid.start(), grid.end()).unwrap();
+
+            let imported = import::parse_ascii(&to_ascii(&grid, &path)).unwrap();
+            assert!(imported.items.is_empty(), "{algorithm} path became items");
+
+            // Imports get a ring of walls around them.
+            let offset = UVec2::ONE;
+            assert_eq!(imported.grid.size(), grid.size() + 2);
+            assert_eq!(imported.grid.start(), grid.start() + offset);
+            assert_eq!(imported.grid.end(), grid.end() + offset);
+            for (pos, tile) in grid.iter() {
+                assert_eq!(
+                    imported.grid.get(pos + offset),
+                    Some(tile),
+                    "{algorithm} at {pos}"
+                );
+            }
+        }
+    }
+}
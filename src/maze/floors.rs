@@ -0,0 +1,249 @@
+//! Mazes with several floors stacked on top of each other, joined by stairs.
+//!
+//! Every floor is its own [`MazeGrid`] with its own tilemap, walls and collision layer.
+//! Only the floor the player is on is shown, and [`CurrentMaze`] always holds that floor.
+
+use std::ops::Index;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::RngCore;
+
+use super::{generate, CurrentMaze, MazeConfig, MazeGrid};
+use crate::{player::Player, state::AppState};
+
+/// Each floor collides on its own layer, and there are only so many of them.
+pub const MAX_FLOORS: u32 = 32;
+
+pub struct FloorsPlugin;
+
+impl Plugin for FloorsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ChangeFloor>().add_systems(
+            Update,
+            (climb_stairs, change_floor)
+                .chain()
+                .in_set(ClimbStairs)
+                .run_if(in_state(AppState::Playing)),
+        );
+    }
+}
+
+/// Moves the player between floors, anything that follows the player's floor should run
+/// after this.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClimbStairs;
+
+/// Every floor of the current maze, from the bottom up.
+///
+/// The player starts at the start of the bottom floor and leaves from the end of the top
+/// floor. The end of every other floor is a staircase up to the start of the floor above,
+/// so as long as each floor connects its start to its end the exit can always be reached.
+#[derive(Resource, Debug, Clone)]
+pub struct MazeFloors {
+    floors: Vec<MazeGrid>,
+    current: usize,
+}
+
+impl MazeFloors {
+    /// A maze with a single floor.
+    pub fn single(grid: MazeGrid) -> Self {
+        Self {
+            floors: vec![grid],
+            current: 0,
+        }
+    }
+
+    /// Generates `config.floors` floors, each one a separate maze.
+    ///
+    /// Every other floor is turned around, so its start lies right above the end of the
+    /// floor below. The bottom floor is the same maze a single floor would be.
+    pub fn generate(config: &MazeConfig, rng: &mut impl RngCore) -> Self {
+        let floors = (0..config.floors.clamp(1, MAX_FLOORS))
+            .map(|floor| {
+                let grid = generate(config, rng);
+                if floor % 2 == 1 {
+                    grid.rotated()
+                } else {
+                    grid
+                }
+            })
+            .collect();
+
+        Self { floors, current: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.floors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.floors.is_empty()
+    }
+
+    /// The floor the player is on.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn top(&self) -> usize {
+        self.floors.len() - 1
+    }
+
+    pub fn get(&self, floor: usize) -> Option<&MazeGrid> {
+        self.floors.get(floor)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MazeGrid> {
+        self.floors.iter()
+    }
+
+    /// Where the stairs at `pos` on `floor` lead, if there are any.
+    pub fn stairs(&self, floor: usize, pos: UVec2) -> Option<(usize, UVec2)> {
+        let grid = self.get(floor)?;
+
+        if pos == grid.end() && floor < self.top() {
+            Some((floor + 1, self.floors[floor + 1].start()))
+        } else if pos == grid.start() && floor > 0 {
+            Some((floor - 1, self.floors[floor - 1].end()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Index<usize> for MazeFloors {
+    type Output = MazeGrid;
+
+    fn index(&self, floor: usize) -> &MazeGrid {
+        &self.floors[floor]
+    }
+}
+
+/// Which floor a tilemap, wall or sensor belongs to.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Deref)]
+pub struct MazeFloor(pub usize);
+
+/// Collision layers for walls and sensors on `floor`.
+pub fn floor_layers(floor: usize) -> CollisionLayers {
+    CollisionLayers::new(LayerMask(1 << floor), LayerMask::ALL)
+}
+
+/// Collision layers for the player while on `floor`, so only that floor's walls block it.
+pub fn player_layers(floor: usize) -> CollisionLayers {
+    CollisionLayers::new(LayerMask::ALL, LayerMask(1 << floor))
+}
+
+/// Moves the player to `position` on `floor`, showing that floor and hiding the others.
+#[derive(Event, Debug, Clone)]
+pub struct ChangeFloor {
+    pub floor: usize,
+    pub position: Vec2,
+}
+
+/// Takes the player up or down whenever they step onto stairs.
+fn climb_stairs(
+    player: Query<&Transform, With<Player>>,
+    floors: Res<MazeFloors>,
+    config: Res<MazeConfig>,
+    mut last: Local<Option<(usize, UVec2)>>,
+    mut changes: EventWriter<ChangeFloor>,
+) {
+    let Some(transform) = player.iter().next() else {
+        return;
+    };
+    let floor = floors.current();
+    let Some(tile) =
+        floors[floor].world_to_tile(transform.translation.truncate(), config.tile_size)
+    else {
+        return;
+    };
+
+    // Arriving on stairs from another floor doesn't count, the player has to step off first.
+    let previous = last.replace((floor, tile));
+    if previous.is_none_or(|previous| previous.0 != floor || previous.1 == tile) {
+        return;
+    }
+
+    if let Some((to, pos)) = floors.stairs(floor, tile) {
+        changes.send(ChangeFloor {
+            floor: to,
+            position: floors[to].tile_to_world(pos, config.tile_size),
+        });
+    }
+}
+
+fn change_floor(
+    mut changes: EventReader<ChangeFloor>,
+    mut floors: ResMut<MazeFloors>,
+    mut maze: ResMut<CurrentMaze>,
+    mut tilemaps: Query<(&MazeFloor, &mut Visibility)>,
+    mut player: Query<(&mut Transform, &mut CollisionLayers), With<Player>>,
+) {
+    let Some(change) = changes.read().last() else {
+        return;
+    };
+    let Some(grid) = floors.get(change.floor) else {
+        warn!("there is no floor {} to move to", change.floor + 1);
+        return;
+    };
+
+    info!("moving to floor {}", change.floor + 1);
+    maze.0 = grid.clone();
+    floors.current = change.floor;
+
+    for (floor, mut visibility) in tilemaps.iter_mut() {
+        *visibility = if **floor == change.floor {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for (mut transform, mut layers) in player.iter_mut() {
+        transform.translation = change.position.extend(transform.translation.z);
+        *layers = player_layers(change.floor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{solve, MazeSeed, Topology};
+
+    #[test]
+    fn stairs_lead_from_the_start_to_the_top_floor_end() {
+        for topology in Topology::ALL {
+            for count in 1..=3 {
+                let config = MazeConfig {
+                    floors: count,
+                    topology,
+                    ..Default::default()
+                };
+                let floors = MazeFloors::generate(&config, &mut MazeSeed(4).rng());
+                assert_eq!(floors.len(), count as usize);
+                assert_eq!(floors.stairs(0, floors[0].start()), None);
+
+                let (mut floor, mut pos) = (0, floors[0].start());
+                loop {
+                    let grid = &floors[floor];
+                    assert!(
+                        solve::bfs(grid, pos, grid.end()).is_some(),
+                        "{topology} floor {floor} of {count} has no way to its end"
+                    );
+                    let Some((up, start)) = floors.stairs(floor, grid.end()) else {
+                        break;
+                    };
+
+                    assert_eq!(up, floor + 1);
+                    assert_eq!(start, floors[up].start());
+                    // The stairs come out right above where they went in, and lead back down.
+                    assert_eq!(start, grid.end(), "{topology} floor {up} of {count}");
+                    assert_eq!(floors.stairs(up, start), Some((floor, grid.end())));
+                    (floor, pos) = (up, start);
+                }
+                assert_eq!(floor, floors.top(), "{topology} with {count} floors");
+            }
+        }
+    }
+}
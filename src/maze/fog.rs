@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::{
+    floors::{ChangeFloor, ClimbStairs},
+    sight, CurrentMaze, MazeConfig, MazeFloor, MazeFloors, SpawnMaze,
+};
+use crate::{player::Player, state::AppState};
+
+/// Tint for tiles that have been seen before but are not currently in sight.
+const EXPLORED_COLOR: Color = Color::srgb(0.3, 0.3, 0.4);
+
+pub struct FogPlugin;
+
+impl Plugin for FogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FogOfWar>()
+            .init_resource::<SightRadius>()
+            .add_systems(OnEnter(AppState::Loading), reset_fog.after(SpawnMaze))
+            .add_systems(
+                Update,
+                (switch_floor, reveal)
+                    .chain()
+                    .after(ClimbStairs)
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// How far the player can see, in tiles.
+#[derive(Resource, Debug, Clone, Copy, Deref)]
+pub struct SightRadius(pub u32);
+
+impl Default for SightRadius {
+    fn default() -> Self {
+        Self(7)
+    }
+}
+
+/// Which tiles of every floor the player has seen, and which tiles of the [`CurrentMaze`]
+/// are in sight right now.
+#[derive(Resource, Debug, Default)]
+pub struct FogOfWar {
+    width: u32,
+    height: u32,
+    /// The floor the player is on, see [`MazeFloors`].
+    floor: usize,
+    explored: Vec<bool>,
+    visible: Vec<UVec2>,
+    /// The tile sight was last calculated from.
+    origin: Option<UVec2>,
+}
+
+impl FogOfWar {
+    pub fn new(width: u32, height: u32, floors: usize) -> Self {
+        Self {
+            width,
+            height,
+            floor: 0,
+            explored: vec![false; (width * height) as usize * floors],
+            visible: Vec::new(),
+            origin: None,
+        }
+    }
+
+    /// Index of `pos` on `floor` in [`Self::explored`].
+    fn index(&self, floor: usize, pos: UVec2) -> Option<usize> {
+        (pos.x < self.width && pos.y < self.height).then(|| {
+            floor * (self.width * self.height) as usize + (pos.y * self.width + pos.x) as usize
+        })
+    }
+
+    /// Whether `pos` on the current floor has been seen.
+    pub fn is_explored(&self, pos: UVec2) -> bool {
+        self.index(self.floor, pos)
+            .and_then(|i| self.explored.get(i))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether each tile has been seen, floor by floor and row by row from the bottom left.
+    pub fn explored(&self) -> &[bool] {
+        &self.explored
+    }
+
+    pub fn visible(&self) -> &[UVec2] {
+        &self.visible
+    }
+
+    /// The tile the player was on when sight was last updated.
+    pub fn origin(&self) -> Option<UVec2> {
+        self.origin
+    }
+
+    /// Replaces the explored tiles, for example from a save.
+    ///
+    /// Returns false and leaves the fog untouched if `explored` is the wrong size.
+    pub fn restore(&mut self, explored: Vec<bool>) -> bool {
+        if explored.len() != self.explored.len() {
+            return false;
+        }
+
+        self.explored = explored;
+        self.visible.clear();
+        self.origin = None;
+        true
+    }
+}
+
+/// Shows every explored tile of `floor` as seen before, used after the fog has been restored.
+pub fn show_explored(
+    fog: &FogOfWar,
+    floor: usize,
+    storage: &TileStorage,
+    tiles: &mut Query<(&mut TileVisible, &mut TileColor)>,
+) {
+    let len = (fog.width * fog.height) as usize;
+    let explored = fog
+        .explored
+        .chunks(len.max(1))
+        .nth(floor)
+        .unwrap_or_default();
+
+    for (i, _) in explored.iter().enumerate().filter(|(_, e)| **e) {
+        let pos = UVec2::new(i as u32 % fog.width, i as u32 / fog.width);
+        if let Some(Ok((mut visible, mut color))) = storage
+            .get(&TilePos::from(pos))
+            .map(|tile| tiles.get_mut(tile))
+        {
+            visible.0 = true;
+            color.0 = EXPLORED_COLOR;
+        }
+    }
+}
+
+fn reset_fog(mut fog: ResMut<FogOfWar>, maze: Res<CurrentMaze>, floors: Res<MazeFloors>) {
+    *fog = FogOfWar::new(maze.width(), maze.height(), floors.len());
+}
+
+/// Dims whatever was in sight on the floor the player is leaving.
+fn switch_floor(
+    mut changes: EventReader<ChangeFloor>,
+    mut fog: ResMut<FogOfWar>,
+    storage: Query<(&MazeFloor, &TileStorage)>,
+    mut tiles: Query<(&mut TileVisible, &mut TileColor)>,
+) {
+    let Some(change) = changes.read().last() else {
+        return;
+    };
+
+    if let Some((_, storage)) = storage.iter().find(|(floor, _)| ***floor == fog.floor) {
+        for pos in fog.visible.iter() {
+            if let Some(Ok((_, mut color))) = storage
+                .get(&TilePos::from(*pos))
+                .map(|tile| tiles.get_mut(tile))
+            {
+                color.0 = EXPLORED_COLOR;
+            }
+        }
+    }
+
+    fog.floor = change.floor;
+    fog.visible.clear();
+    fog.origin = None;
+}
+
+fn reveal(
+    mut fog: ResMut<FogOfWar>,
+    player: Query<&Transform, With<Player>>,
+    config: Res<MazeConfig>,
+    maze: Res<CurrentMaze>,
+    sight: Res<SightRadius>,
+    storage: Query<(&MazeFloor, &TileStorage)>,
+    mut tiles: Query<(&mut TileVisible, &mut TileColor)>,
+) {
+    let floor = storage.iter().find(|(floor, _)| ***floor == fog.floor);
+    let (Some(transform), Some((_, storage))) = (player.iter().next(), floor) else {
+        return;
+    };
+
+    let Some(origin) = maze.world_to_tile(transform.translation.truncate(), config.tile_size)
+    else {
+        return;
+    };
+    if fog.origin == Some(origin) {
+        return;
+    }
+    fog.origin = Some(origin);
+
+    for pos in std::mem::take(&mut fog.visible) {
+        if let Some(Ok((_, mut color))) = storage
+            .get(&TilePos::from(pos))
+            .map(|tile| tiles.get_mut(tile))
+        {
+            color.0 = EXPLORED_COLOR;
+        }
+    }
+
+    let visible = sight::line_of_sight(&maze, origin, sight.0);
+    for pos in visible.iter() {
+        if let Some(index) = fog.index(fog.floor, *pos) {
+            fog.explored[index] = true;
+        }
+
+        if let Some(Ok((mut tile_visible, mut color))) = storage
+            .get(&TilePos::from(*pos))
+            .map(|tile| tiles.get_mut(tile))
+        {
+            tile_visible.0 = true;
+            color.0 = Color::WHITE;
+        }
+    }
+    fog.visible = visible;
+}
@@ -0,0 +1,226 @@
+#[cfg(feature = "game")]
+use bevy::prelude::Resource;
+use glam::UVec2;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    algorithms::MazeAlgorithm,
+    braid::braid,
+    cells::{CellGrid, Direction},
+    grid::{MazeGrid, TileType},
+    topology::Topology,
+};
+
+#[cfg_attr(feature = "game", derive(Resource))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MazeConfig {
+    /// Width of the maze in logical cells, including the solid outer edge.
+    pub width: u32,
+    /// Height of the maze in logical cells, including the solid outer edge.
+    pub height: u32,
+    /// Every logical cell becomes an `expansion` x `expansion` block of tiles.
+    ///
+    /// Walls between cells are always two tiles thick, so corridors are `expansion - 2` tiles wide.
+    /// Hex corridors are always one tile wide, with `expansion` tiles between cell centres.
+    pub expansion: u32,
+    /// Size of a single tile in world units.
+    pub tile_size: f32,
+    pub algorithm: MazeAlgorithm,
+    /// Percentage of dead ends to open up into loops, see [`braid`].
+    #[serde(default)]
+    pub braid: u32,
+    /// Number of floors stacked on top of each other, see [`super::floors`].
+    #[serde(default = "default_floors")]
+    pub floors: u32,
+    #[serde(default)]
+    pub topology: Topology,
+}
+
+pub(crate) fn default_floors() -> u32 {
+    1
+}
+
+impl Default for MazeConfig {
+    fn default() -> Self {
+        Self {
+            width: 16,
+            height: 16,
+            expansion: 3,
+            tile_size: 16.,
+            algorithm: MazeAlgorithm::default(),
+            braid: 0,
+            floors: default_floors(),
+            topology: Topology::default(),
+        }
+    }
+}
+
+impl MazeConfig {
+    /// Size of the expanded maze in tiles.
+    pub fn map_size(&self) -> UVec2 {
+        UVec2::new(self.width, self.height) * self.expansion
+    }
+}
+
+/// Generates a maze from the bottom left to the top right with `config.algorithm`.
+///
+/// The outermost ring of cells is always left solid. With a `config.braid` above 0 the
+/// maze has loops, so there is more than one route to the end.
+///
+/// # Panics
+///
+/// Panics if the maze is smaller than 3x3 cells or `expansion` is less than 3.
+pub fn generate(config: &MazeConfig, rng: &mut impl RngCore) -> MazeGrid {
+    assert!(
+        config.width >= 3 && config.height >= 3,
+        "maze must be at least 3x3 cells, got {}x{}",
+        config.width,
+        config.height
+    );
+    assert!(
+        config.expansion >= 3,
+        "expansion must be at least 3, got {}",
+        config.expansion
+    );
+
+    let mut cells = CellGrid::with_topology(config.width - 2, config.height - 2, config.topology);
+    config.algorithm.generator().carve(&mut cells, rng);
+    braid(&mut cells, config.braid, rng);
+    expand(config, &cells)
+}
+
+/// Expands the logical cells into tiles, carving a corridor through the center of every
+/// visited cell and between every pair of linked cells.
+pub fn expand(config: &MazeConfig, cells: &CellGrid) -> MazeGrid {
+    let size = config.map_size();
+    let mut grid = MazeGrid::with_topology(size.x, size.y, cells.topology());
+
+    if cells.topology() == Topology::Hex {
+        expand_hex(config, cells, &mut grid);
+        return grid;
+    }
+
+    for cell in 0..cells.len() {
+        let (min, max) = corridor(config, cells, cell);
+        if cells.is_visited(cell) || cell == cells.start() || cell == cells.end() {
+            carve_rect(&mut grid, min, max);
+        }
+
+        for dir in [Direction::Up, Direction::Right] {
+            if let Some(neighbor) = cells
+                .neighbor(cell, dir)
+                .filter(|_| cells.is_linked(cell, dir))
+            {
+                let (_, neighbor_max) = corridor(config, cells, neighbor);
+                carve_rect(&mut grid, min, neighbor_max);
+            }
+        }
+    }
+
+    grid.set_start(corridor_center(config, cells, cells.start()));
+    grid.set_end(corridor_center(config, cells, cells.end()));
+
+    grid
+}
+
+/// Hex corridors are a single line of tiles from cell centre to cell centre. Any wider and
+/// the corridors around a shared corner would run into each other.
+fn expand_hex(config: &MazeConfig, cells: &CellGrid, grid: &mut MazeGrid) {
+    for cell in 0..cells.len() {
+        let center = corridor_center(config, cells, cell);
+        if cells.is_visited(cell) || cell == cells.start() || cell == cells.end() {
+            grid.set(center, TileType::Floor);
+        }
+
+        // One direction of each opposite pair, so every link is carved from one of its cells.
+        for dir in [Direction::Up, Direction::Right, Direction::UpLeft] {
+            if cells.is_linked(cell, dir) {
+                for step in 1..config.expansion as i32 {
+                    let pos = center.as_ivec2() + dir.offset() * step;
+                    grid.set(pos.as_uvec2(), TileType::Floor);
+                }
+            }
+        }
+    }
+
+    grid.set_start(corridor_center(config, cells, cells.start()));
+    grid.set_end(corridor_center(config, cells, cells.end()));
+}
+
+/// The inclusive bounds of the tiles making up the corridor through `cell`.
+fn corridor(config: &MazeConfig, cells: &CellGrid, cell: usize) -> (UVec2, UVec2) {
+    // Offset by one cell for the solid outer edge.
+    let origin = (cells.pos(cell) + 1) * config.expansion;
+    (origin + 1, origin + config.expansion - 2)
+}
+
+fn corridor_center(config: &MazeConfig, cells: &CellGrid, cell: usize) -> UVec2 {
+    let (min, max) = corridor(config, cells, cell);
+    (min + max) / 2
+}
+
+fn carve_rect(grid: &mut MazeGrid, min: UVec2, max: UVec2) {
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            grid.set(UVec2::new(x, y), TileType::Floor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::MazeSeed;
+
+    /// FNV-1a over the tiles, start and end, which unlike `DefaultHasher` is stable
+    /// across Rust releases.
+    fn hash(grid: &MazeGrid) -> u64 {
+        let tiles = grid.tiles().iter().map(|tile| *tile as u8);
+        let ends = [grid.start(), grid.end()]
+            .into_iter()
+            .flat_map(|pos| pos.to_array())
+            .flat_map(u32::to_le_bytes);
+
+        tiles.chain(ends).fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+
+    #[test]
+    fn same_seed_same_maze() {
+        for algorithm in MazeAlgorithm::ALL {
+            let config = MazeConfig {
+                algorithm,
+                ..Default::default()
+            };
+            let first = generate(&config, &mut MazeSeed(42).rng());
+            let second = generate(&config, &mut MazeSeed(42).rng());
+            assert_eq!(first, second, "{algorithm} is not deterministic");
+        }
+    }
+
+    #[test]
+    fn seeded_mazes_are_stable() {
+        let expected = [
+            (MazeAlgorithm::RandomWalk, 10814415565585242781),
+            (MazeAlgorithm::RecursiveBacktracker, 686261063103770593),
+            (MazeAlgorithm::Prim, 6937678086934641419),
+            (MazeAlgorithm::Kruskal, 586797571581788239),
+            (MazeAlgorithm::Wilson, 15403158995383646941),
+            (MazeAlgorithm::Eller, 12905768844797113689),
+            (MazeAlgorithm::BinaryTree, 2449591104981316139),
+            (MazeAlgorithm::Sidewinder, 16500476311880211261),
+        ];
+        assert_eq!(expected.len(), MazeAlgorithm::ALL.len());
+
+        for (algorithm, expected) in expected {
+            let config = MazeConfig {
+                algorithm,
+                ..Default::default()
+            };
+            let grid = generate(&config, &mut MazeSeed(42).rng());
+            assert_eq!(hash(&grid), expected, "{algorithm} changed for seed 42");
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, time::Stopwatch};
+
+use super::MazeSeed;
+use crate::{player::Player, state::AppState};
+
+pub struct GoalPlugin;
+
+impl Plugin for GoalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<MazeCompleted>()
+            .init_resource::<LevelTimer>()
+            .add_systems(
+                Update,
+                (tick_level_timer, reach_goal).run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Marks the sensor at the exit of the maze.
+#[derive(Component)]
+pub struct Goal;
+
+/// Sent when the player reaches the [`Goal`].
+#[derive(Event, Debug, Clone)]
+pub struct MazeCompleted {
+    pub seed: MazeSeed,
+    pub elapsed: Duration,
+}
+
+/// Time spent in the current maze.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct LevelTimer(pub Stopwatch);
+
+fn tick_level_timer(time: Res<Time>, mut timer: ResMut<LevelTimer>) {
+    timer.tick(time.delta());
+}
+
+pub fn reset_level_timer(mut timer: ResMut<LevelTimer>) {
+    timer.reset();
+}
+
+fn reach_goal(
+    mut collisions: EventReader<CollisionStarted>,
+    goals: Query<(), With<Goal>>,
+    players: Query<(), With<Player>>,
+    timer: Res<LevelTimer>,
+    seed: Res<MazeSeed>,
+    mut completed: EventWriter<MazeCompleted>,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        let reached = (goals.contains(*a) && players.contains(*b))
+            || (goals.contains(*b) && players.contains(*a));
+
+        if reached {
+            info!("completed maze {} in {:.2}s", *seed, timer.elapsed_secs());
+            completed.send(MazeCompleted {
+                seed: *seed,
+                elapsed: timer.elapsed(),
+            });
+        }
+    }
+}
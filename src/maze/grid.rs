@@ -0,0 +1,165 @@
+use glam::{UVec2, Vec2};
+use serde::{Deserialize, Serialize};
+
+use super::topology::Topology;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TileType {
+    Wall,
+    Floor,
+}
+
+/// The expanded tile layout of a maze, independent of any ECS state.
+///
+/// Tiles are stored row by row starting from the bottom left, the same layout
+/// the rendered tilemap uses for its `TilePos`. The [`Topology`] decides which tiles are
+/// next to each other and where they are in the world.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MazeGrid {
+    width: u32,
+    height: u32,
+    topology: Topology,
+    tiles: Vec<TileType>,
+    start: UVec2,
+    end: UVec2,
+}
+
+impl MazeGrid {
+    /// Creates a grid of the given size filled entirely with walls.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_topology(width, height, Topology::Square)
+    }
+
+    pub fn with_topology(width: u32, height: u32, topology: Topology) -> Self {
+        Self {
+            width,
+            height,
+            topology,
+            tiles: vec![TileType::Wall; (width * height) as usize],
+            start: UVec2::ZERO,
+            end: UVec2::ZERO,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn size(&self) -> UVec2 {
+        UVec2::new(self.width, self.height)
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn start(&self) -> UVec2 {
+        self.start
+    }
+
+    pub fn end(&self) -> UVec2 {
+        self.end
+    }
+
+    pub fn set_start(&mut self, start: UVec2) {
+        self.start = start;
+    }
+
+    pub fn set_end(&mut self, end: UVec2) {
+        self.end = end;
+    }
+
+    /// The world position of the center of the tile at `pos`, with the maze centered on the origin.
+    pub fn tile_to_world(&self, pos: UVec2, tile_size: f32) -> Vec2 {
+        self.topology.project(pos.as_vec2(), tile_size) - self.center(tile_size)
+    }
+
+    /// The tile containing the world position `world`, if it is inside the maze.
+    pub fn world_to_tile(&self, world: Vec2, tile_size: f32) -> Option<UVec2> {
+        let pos = self.topology.round(
+            self.topology
+                .unproject(world + self.center(tile_size), tile_size),
+        );
+        (pos.x >= 0 && pos.y >= 0 && self.contains(pos.as_uvec2())).then(|| pos.as_uvec2())
+    }
+
+    /// Halfway between the first and last tile, before centering the maze on the origin.
+    fn center(&self, tile_size: f32) -> Vec2 {
+        self.topology.project(self.size().as_vec2() - 1., tile_size) * 0.5
+    }
+
+    pub fn contains(&self, pos: UVec2) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn index(&self, pos: UVec2) -> usize {
+        (pos.y * self.width + pos.x) as usize
+    }
+
+    pub fn pos(&self, index: usize) -> UVec2 {
+        UVec2::new(index as u32 % self.width, index as u32 / self.width)
+    }
+
+    pub fn get(&self, pos: UVec2) -> Option<TileType> {
+        self.contains(pos).then(|| self.tiles[self.index(pos)])
+    }
+
+    pub fn set(&mut self, pos: UVec2, tile: TileType) {
+        let index = self.index(pos);
+        self.tiles[index] = tile;
+    }
+
+    /// Anything outside of the grid counts as a wall.
+    pub fn is_wall(&self, pos: UVec2) -> bool {
+        self.get(pos).is_none_or(|tile| tile == TileType::Wall)
+    }
+
+    pub fn is_floor(&self, pos: UVec2) -> bool {
+        self.get(pos) == Some(TileType::Floor)
+    }
+
+    /// The tiles of the grid next to `pos`.
+    pub fn neighbors(&self, pos: UVec2) -> impl Iterator<Item = UVec2> + '_ {
+        self.topology
+            .offsets()
+            .iter()
+            .map(move |offset| pos.as_ivec2() + *offset)
+            .filter(|pos| pos.x >= 0 && pos.y >= 0)
+            .map(|pos| pos.as_uvec2())
+            .filter(|pos| self.contains(*pos))
+    }
+
+    /// The floor tiles next to `pos`.
+    pub fn floor_neighbors(&self, pos: UVec2) -> impl Iterator<Item = UVec2> + '_ {
+        self.neighbors(pos).filter(|pos| self.is_floor(*pos))
+    }
+
+    /// The grid turned half way around, so the bottom left ends up in the top right.
+    pub fn rotated(&self) -> Self {
+        let flip = |pos: UVec2| self.size() - 1 - pos;
+        Self {
+            width: self.width,
+            height: self.height,
+            topology: self.topology,
+            tiles: self.tiles.iter().rev().copied().collect(),
+            start: flip(self.start),
+            end: flip(self.end),
+        }
+    }
+
+    pub fn tiles(&self) -> &[TileType] {
+        &self.tiles
+    }
+
+    /// Iterates over every tile along with its position.
+    pub fn iter(&self) -> impl Iterator<Item = (UVec2, TileType)> + '_ {
+        self.tiles
+            .iter()
+            .enumerate()
+            .map(|(i, tile)| (self.pos(i), *tile))
+    }
+}
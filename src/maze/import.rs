@@ -0,0 +1,406 @@
+use std::{error::Error, fmt, io};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use image::{ImageFormat, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use super::grid::{MazeGrid, TileType};
+use crate::state::AppState;
+
+/// Environment variable a hand authored maze can be read from.
+const MAZE_ENV_VAR: &str = "MAZE_FILE";
+/// Command line flag a hand authored maze can be read from, takes precedence over [`MAZE_ENV_VAR`].
+const MAZE_ARG: &str = "--maze";
+
+pub struct ImportPlugin;
+
+impl Plugin for ImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MazeAsset>()
+            .init_asset_loader::<MazeAssetLoader>()
+            .add_systems(Startup, load_custom_maze)
+            .add_systems(
+                Update,
+                reload_custom_maze.run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// A hand authored maze, loaded from a `.maze` text file or a `.maze.png` image.
+///
+/// Text mazes use `#` for walls, `.` for floors, `S` for the start and `E` for the exit,
+/// and `o` for floors on the critical path as exported by [`super::export::to_ascii`].
+/// Any other glyph is a floor tile holding an item. The first line is the top of the maze.
+/// Items are drawn on their tile, whatever their glyph, but can't be picked up yet.
+///
+/// Images use black for walls, white for floors, green for the start and red for the exit,
+/// other colors are matched to the closest of these and transparent pixels are walls.
+///
+/// Either way a solid ring of walls is added around the layout so the player can't walk off it.
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct MazeAsset {
+    pub grid: MazeGrid,
+    pub items: Vec<MazeItem>,
+}
+
+/// A glyph placed on a floor tile of a [`MazeAsset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MazeItem {
+    pub pos: UVec2,
+    pub glyph: char,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    Image(image::ImageError),
+    Empty,
+    /// A blank line inside a text maze, counting lines from 1.
+    BlankLine(usize),
+    MissingStart,
+    MissingEnd,
+    /// More than one start or exit, at the given tile.
+    Duplicate {
+        glyph: char,
+        pos: UVec2,
+    },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read maze: {e}"),
+            Self::Image(e) => write!(f, "failed to decode maze image: {e}"),
+            Self::Empty => write!(f, "maze is empty"),
+            Self::BlankLine(line) => write!(f, "maze has a blank line at line {line}"),
+            Self::MissingStart => write!(f, "maze has no start"),
+            Self::MissingEnd => write!(f, "maze has no exit"),
+            Self::Duplicate { glyph, pos } => {
+                write!(
+                    f,
+                    "maze has more than one {glyph:?}, found another at {pos}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Image(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<image::ImageError> for ImportError {
+    fn from(e: image::ImageError) -> Self {
+        Self::Image(e)
+    }
+}
+
+/// What a single glyph or pixel of an imported maze stands for.
+#[derive(Clone, Copy)]
+enum Cell {
+    Wall,
+    Floor,
+    Start,
+    End,
+    Item(char),
+}
+
+/// Builds a maze from rows of cells, listed from the top down.
+fn build(rows: Vec<Vec<Cell>>) -> Result<MazeAsset, ImportError> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default() as u32;
+    let height = rows.len() as u32;
+    if width == 0 {
+        return Err(ImportError::Empty);
+    }
+
+    let mut grid = MazeGrid::new(width + 2, height + 2);
+    let mut items = Vec::new();
+    let (mut start, mut end) = (None, None);
+
+    for (row, cells) in rows.into_iter().enumerate() {
+        for (x, cell) in cells.into_iter().enumerate() {
+            let pos = UVec2::new(x as u32 + 1, height - row as u32);
+
+            let marker = match cell {
+                Cell::Wall => continue,
+                Cell::Floor => None,
+                Cell::Start => Some(('S', &mut start)),
+                Cell::End => Some(('E', &mut end)),
+                Cell::Item(glyph) => {
+                    items.push(MazeItem { pos, glyph });
+                    None
+                }
+            };
+
+            if let Some((glyph, marker)) = marker {
+                if marker.replace(pos).is_some() {
+                    return Err(ImportError::Duplicate { glyph, pos });
+                }
+            }
+
+            grid.set(pos, TileType::Floor);
+        }
+    }
+
+    grid.set_start(start.ok_or(ImportError::MissingStart)?);
+    grid.set_end(end.ok_or(ImportError::MissingEnd)?);
+    Ok(MazeAsset { grid, items })
+}
+
+/// Parses a text maze, see [`MazeAsset`].
+///
+/// Blank lines before and after the maze are ignored, but not inside of it since every
+/// row below would move up.
+pub fn parse_ascii(text: &str) -> Result<MazeAsset, ImportError> {
+    let lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return Err(ImportError::Empty);
+    };
+
+    if let Some(blank) = (first..=last).find(|i| lines[*i].is_empty()) {
+        return Err(ImportError::BlankLine(blank + 1));
+    }
+
+    let rows = lines[first..=last]
+        .iter()
+        .map(|line| {
+            line.chars()
+                .map(|glyph| match glyph {
+                    '#' | ' ' => Cell::Wall,
+                    '.' | 'o' => Cell::Floor,
+                    'S' => Cell::Start,
+                    'E' => Cell::End,
+                    glyph => Cell::Item(glyph),
+                })
+                .collect()
+        })
+        .collect();
+
+    build(rows)
+}
+
+/// Reads an image maze, see [`MazeAsset`].
+pub fn from_image(image: &RgbaImage) -> Result<MazeAsset, ImportError> {
+    const PALETTE: [([u8; 3], Cell); 4] = [
+        ([0, 0, 0], Cell::Wall),
+        ([255, 255, 255], Cell::Floor),
+        ([0, 255, 0], Cell::Start),
+        ([255, 0, 0], Cell::End),
+    ];
+
+    let classify = |Rgba([r, g, b, a]): Rgba<u8>| {
+        if a < 128 {
+            return Cell::Wall;
+        }
+
+        let distance = |color: [u8; 3]| {
+            [r, g, b]
+                .iter()
+                .zip(color)
+                .map(|(a, b)| (*a as i32 - b as i32).pow(2))
+                .sum::<i32>()
+        };
+        let (_, cell) = PALETTE
+            .iter()
+            .min_by_key(|(color, _)| distance(*color))
+            .unwrap();
+        *cell
+    };
+
+    let rows = image
+        .rows()
+        .map(|row| row.map(|pixel| classify(*pixel)).collect())
+        .collect();
+
+    build(rows)
+}
+
+#[derive(Default)]
+pub struct MazeAssetLoader;
+
+impl AssetLoader for MazeAssetLoader {
+    type Asset = MazeAsset;
+    type Settings = ();
+    type Error = ImportError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<MazeAsset, ImportError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let is_image = load_context
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "png");
+
+        if is_image {
+            let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png)?;
+            from_image(&image.to_rgba8())
+        } else {
+            let text = String::from_utf8(bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            parse_ascii(&text)
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["maze", "maze.png"]
+    }
+}
+
+/// The hand authored maze to play instead of generating one, if any.
+#[derive(Resource, Debug, Default)]
+pub struct CustomMaze(pub Option<Handle<MazeAsset>>);
+
+impl CustomMaze {
+    /// The loaded maze, `None` if there is no custom maze or it hasn't finished loading.
+    pub fn get<'a>(&self, assets: &'a Assets<MazeAsset>) -> Option<&'a MazeAsset> {
+        self.0.as_ref().and_then(|handle| assets.get(handle))
+    }
+}
+
+/// Reads the maze path, relative to the assets folder, from `--maze <path>` or the
+/// `MAZE_FILE` environment variable.
+fn load_custom_maze(mut commands: Commands, server: Res<AssetServer>) {
+    let mut args = std::env::args().skip_while(|arg| arg != MAZE_ARG).skip(1);
+    let path = args.next().or_else(|| std::env::var(MAZE_ENV_VAR).ok());
+
+    commands.insert_resource(CustomMaze(path.map(|path| {
+        info!("using custom maze {path}");
+        server.load(path)
+    })));
+}
+
+/// Restarts the level whenever the custom maze is changed on disk.
+fn reload_custom_maze(
+    mut events: EventReader<AssetEvent<MazeAsset>>,
+    custom: Res<CustomMaze>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(handle) = &custom.0 else {
+        return;
+    };
+
+    if events.read().any(|event| {
+        event.is_modified(handle.id()) || event.is_loaded_with_dependencies(handle.id())
+    }) {
+        info!("custom maze changed, reloading");
+        next_state.set(AppState::Loading);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_line_is_the_top() {
+        let maze = parse_ascii("S..\n#.#\n..E").unwrap();
+        let grid = &maze.grid;
+
+        // Surrounded by a ring of walls, so everything moves one tile in.
+        assert_eq!(grid.size(), UVec2::new(5, 5));
+        assert_eq!(grid.start(), UVec2::new(1, 3));
+        assert_eq!(grid.end(), UVec2::new(3, 1));
+        assert!(grid.is_wall(UVec2::new(1, 2)));
+        assert!(grid.is_floor(UVec2::new(2, 2)));
+        assert!((0..5).all(|i| grid.is_wall(UVec2::new(i, 0)) && grid.is_wall(UVec2::new(0, i))));
+    }
+
+    #[test]
+    fn ragged_rows_are_padded_with_walls() {
+        let maze = parse_ascii("S....\n.\n...E").unwrap();
+        let grid = &maze.grid;
+
+        assert_eq!(grid.size(), UVec2::new(7, 5));
+        assert!(grid.is_floor(UVec2::new(1, 2)));
+        assert!(grid.is_wall(UVec2::new(2, 2)));
+        assert!(grid.is_wall(UVec2::new(5, 1)));
+    }
+
+    #[test]
+    fn unknown_glyphs_are_items_on_floor() {
+        let maze = parse_ascii("S*\nk!E").unwrap();
+
+        assert_eq!(
+            maze.items,
+            [
+                MazeItem {
+                    pos: UVec2::new(2, 2),
+                    glyph: '*'
+                },
+                MazeItem {
+                    pos: UVec2::new(1, 1),
+                    glyph: 'k'
+                },
+                MazeItem {
+                    pos: UVec2::new(2, 1),
+                    glyph: '!'
+                },
+            ]
+        );
+        assert!(maze.items.iter().all(|item| maze.grid.is_floor(item.pos)));
+    }
+
+    #[test]
+    fn start_and_end_are_required_once() {
+        assert!(matches!(parse_ascii("..E"), Err(ImportError::MissingStart)));
+        assert!(matches!(parse_ascii("S.."), Err(ImportError::MissingEnd)));
+        assert!(matches!(
+            parse_ascii("S.S\n..E"),
+            Err(ImportError::Duplicate { glyph: 'S', pos }) if pos == UVec2::new(3, 2)
+        ));
+        assert!(matches!(
+            parse_ascii("S.E\nE.."),
+            Err(ImportError::Duplicate { glyph: 'E', .. })
+        ));
+        assert!(matches!(parse_ascii(""), Err(ImportError::Empty)));
+        assert!(matches!(parse_ascii("\n  \n"), Err(ImportError::Empty)));
+    }
+
+    #[test]
+    fn only_outer_blank_lines_are_allowed() {
+        let maze = parse_ascii("\n\nS.\n.E\n\n").unwrap();
+        assert_eq!(maze.grid.size(), UVec2::new(4, 4));
+
+        assert!(matches!(
+            parse_ascii("\nS.\n\n.E"),
+            Err(ImportError::BlankLine(3))
+        ));
+    }
+
+    #[test]
+    fn images_match_text() {
+        let mut image = RgbaImage::from_pixel(3, 2, Rgba([0, 0, 0, 255]));
+        image.put_pixel(0, 0, Rgba([0, 255, 0, 255]));
+        image.put_pixel(1, 0, Rgba([250, 250, 240, 255]));
+        image.put_pixel(1, 1, Rgba([255, 255, 255, 255]));
+        image.put_pixel(2, 1, Rgba([230, 20, 10, 255]));
+        image.put_pixel(0, 1, Rgba([255, 255, 255, 0]));
+
+        let from_image = from_image(&image).unwrap();
+        let from_text = parse_ascii("S.#\n#.E").unwrap();
+        assert_eq!(from_image.grid, from_text.grid);
+    }
+}
@@ -0,0 +1,262 @@
+//! Measures of how hard a [`MazeGrid`] is to solve.
+//!
+//! Everything is counted over floor tiles, so corridors should be one tile wide. The layout
+//! of a generated maze only depends on its seed, so generating it with an `expansion` of 3
+//! gives the metrics for any expansion.
+
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+use glam::UVec2;
+use rand::RngCore;
+
+use super::{generate, solve, MazeConfig, MazeGrid, MazeSeed};
+
+/// Tiles of walking a turn along the solution is worth, turns make a route harder to follow.
+const TURN_COST: f32 = 2.;
+/// Tiles of walking per point of difficulty.
+const DIFFICULTY_SCALE: f32 = 100.;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MazeMetrics {
+    pub floor_tiles: usize,
+    /// Tiles on the shortest route from the start to the end, both included.
+    pub solution_length: usize,
+    /// Floor tiles with a single way out.
+    pub dead_ends: usize,
+    /// Floor tiles with three or more ways out.
+    pub junctions: usize,
+    /// Average number of ways out of a junction.
+    pub branching: f32,
+    /// The furthest any floor tile is from the solution.
+    pub max_branch_depth: u32,
+    /// How far dead ends are from the solution on average.
+    pub mean_branch_depth: f32,
+    /// Average length of the corridors between junctions and dead ends, high when the
+    /// maze flows along long passages and low when it is full of short branches.
+    pub river: f32,
+    /// Changes of direction along the solution.
+    pub turns: usize,
+    /// Tiles along the solution where there is more than one way forward.
+    pub decision_points: usize,
+    /// Average number of ways forward at each decision point.
+    pub mean_choices: f32,
+}
+
+impl MazeMetrics {
+    pub fn measure(grid: &MazeGrid) -> Self {
+        let solution = solve::bfs(grid, grid.start(), grid.end()).unwrap_or_default();
+
+        let mut metrics = Self {
+            solution_length: solution.len(),
+            ..Default::default()
+        };
+
+        let (mut node_exits, mut junction_exits) = (0, 0);
+        for (pos, _) in grid.iter().filter(|(pos, _)| grid.is_floor(*pos)) {
+            metrics.floor_tiles += 1;
+            match grid.floor_neighbors(pos).count() {
+                2 => {}
+                exits => {
+                    node_exits += exits;
+                    if exits == 1 {
+                        metrics.dead_ends += 1;
+                    } else if exits >= 3 {
+                        metrics.junctions += 1;
+                        junction_exits += exits;
+                    }
+                }
+            }
+        }
+        metrics.branching = junction_exits as f32 / metrics.junctions.max(1) as f32;
+        // Every corridor joins two nodes, so counts as half the exits of the nodes.
+        metrics.river = metrics.floor_tiles as f32 / (node_exits as f32 / 2.).max(1.);
+
+        metrics.turns = solution
+            .windows(3)
+            .filter(|w| {
+                let a = w[1].as_ivec2() - w[0].as_ivec2();
+                let b = w[2].as_ivec2() - w[1].as_ivec2();
+                a != b
+            })
+            .count();
+
+        let mut choices = 0;
+        for (i, pos) in solution
+            .iter()
+            .enumerate()
+            .take(solution.len().saturating_sub(1))
+        {
+            let exits = grid.floor_neighbors(*pos).count();
+            // Every tile but the start has the way back behind it.
+            let forward = if i == 0 { exits } else { exits - 1 };
+            if forward > 1 {
+                metrics.decision_points += 1;
+                choices += forward;
+            }
+        }
+        metrics.mean_choices = choices as f32 / metrics.decision_points.max(1) as f32;
+
+        let depths = branch_depths(grid, &solution);
+        metrics.max_branch_depth = depths.iter().flatten().copied().max().unwrap_or_default();
+        let dead_end_depths = grid
+            .iter()
+            .filter(|(pos, _)| grid.is_floor(*pos) && grid.floor_neighbors(*pos).count() == 1)
+            .filter_map(|(pos, _)| depths[grid.index(pos)])
+            .collect::<Vec<_>>();
+        metrics.mean_branch_depth =
+            dead_end_depths.iter().sum::<u32>() as f32 / dead_end_depths.len().max(1) as f32;
+
+        metrics
+    }
+
+    /// Wrong ways a player could take on the way to the end.
+    pub fn wrong_turns(&self) -> f32 {
+        self.decision_points as f32 * (self.mean_choices - 1.).max(0.)
+    }
+
+    /// A single score for comparing mazes, roughly how many hundreds of tiles a player
+    /// can expect to walk including going down and back up wrong branches.
+    pub fn difficulty(&self) -> f32 {
+        let route = self.solution_length as f32 + self.turns as f32 * TURN_COST;
+        let detours = self.wrong_turns() * self.mean_branch_depth * 2.;
+        (route + detours) / DIFFICULTY_SCALE
+    }
+}
+
+/// Distance from every floor tile to the nearest tile of `solution`.
+fn branch_depths(grid: &MazeGrid, solution: &[UVec2]) -> Vec<Option<u32>> {
+    let mut depths = vec![None; grid.tiles().len()];
+    let mut queue = VecDeque::new();
+
+    for pos in solution {
+        depths[grid.index(*pos)] = Some(0);
+        queue.push_back(*pos);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let depth = depths[grid.index(current)].unwrap_or_default();
+        for neighbor in grid.floor_neighbors(current) {
+            let index = grid.index(neighbor);
+            if depths[index].is_none() {
+                depths[index] = Some(depth + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    depths
+}
+
+/// Finds a seed whose maze has a difficulty inside `band`, trying `seed` first and then
+/// seeds drawn from it. Gives up after `attempts` and returns the closest.
+pub fn find_seed(
+    config: &MazeConfig,
+    seed: MazeSeed,
+    band: RangeInclusive<f32>,
+    attempts: usize,
+) -> (MazeSeed, MazeMetrics) {
+    let config = MazeConfig {
+        expansion: 3,
+        ..config.clone()
+    };
+    let distance = |metrics: &MazeMetrics| {
+        let difficulty = metrics.difficulty();
+        (band.start() - difficulty)
+            .max(difficulty - band.end())
+            .max(0.)
+    };
+
+    let mut rng = seed.rng();
+    let mut candidate = seed;
+    let mut best: Option<(MazeSeed, MazeMetrics)> = None;
+
+    for _ in 0..attempts.max(1) {
+        let metrics = MazeMetrics::measure(&generate(&config, &mut candidate.rng()));
+        if distance(&metrics) == 0. {
+            return (candidate, metrics);
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|(_, best)| distance(&metrics) < distance(best))
+        {
+            best = Some((candidate, metrics));
+        }
+        candidate = MazeSeed(rng.next_u64());
+    }
+
+    best.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::TileType;
+
+    /// A corridor from the start that turns down into a T-junction, with a dead end to the
+    /// left of the junction and the end around the corner to the right.
+    ///
+    /// ```text
+    /// #######
+    /// #S...##
+    /// ####.##
+    /// #...+.#
+    /// #####E#
+    /// #######
+    /// ```
+    fn corridor() -> MazeGrid {
+        let mut grid = MazeGrid::new(7, 6);
+        let corridor = [(1, 1), (2, 1), (3, 1), (4, 1), (4, 2)];
+        let junction = [(1, 3), (2, 3), (3, 3), (4, 3), (5, 3), (5, 4)];
+        for (x, y) in corridor.into_iter().chain(junction) {
+            grid.set(UVec2::new(x, y), TileType::Floor);
+        }
+        grid.set_start(UVec2::new(1, 1));
+        grid.set_end(UVec2::new(5, 4));
+        grid
+    }
+
+    #[test]
+    fn measures_a_hand_built_maze() {
+        let metrics = MazeMetrics::measure(&corridor());
+
+        assert_eq!(metrics.floor_tiles, 11);
+        assert_eq!(metrics.solution_length, 8);
+        // The start, the end and the branch to the left of the junction.
+        assert_eq!(metrics.dead_ends, 3);
+        assert_eq!(metrics.junctions, 1);
+        assert_eq!(metrics.branching, 3.);
+        assert_eq!(metrics.turns, 3);
+        // Only the junction offers a choice, between the dead end and the way on.
+        assert_eq!(metrics.decision_points, 1);
+        assert_eq!(metrics.mean_choices, 2.);
+        assert_eq!(metrics.max_branch_depth, 3);
+        // The start and end are on the solution, the other dead end is three tiles off it.
+        assert_eq!(metrics.mean_branch_depth, 1.);
+        // Three dead ends and a junction with three exits make three corridors.
+        assert_eq!(metrics.river, 11. / 3.);
+    }
+
+    #[test]
+    fn finds_a_seed_inside_the_band() {
+        let config = MazeConfig {
+            width: 8,
+            height: 8,
+            // What `find_seed` measures at.
+            expansion: 3,
+            ..Default::default()
+        };
+        let measure = |seed: MazeSeed| MazeMetrics::measure(&generate(&config, &mut seed.rng()));
+
+        // The third seed `find_seed` tries, so it has to look past the first ones.
+        let mut rng = MazeSeed(1).rng();
+        rng.next_u64();
+        let target = MazeSeed(rng.next_u64());
+        let difficulty = measure(target).difficulty();
+        assert_ne!(measure(MazeSeed(1)).difficulty(), difficulty);
+
+        let (seed, metrics) = find_seed(&config, MazeSeed(1), difficulty..=difficulty, 10);
+        assert_eq!(metrics.difficulty(), difficulty);
+        assert_eq!(metrics, measure(seed));
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+#[cfg(feature = "game")]
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable the starting seed can be read from.
+#[cfg(feature = "game")]
+const SEED_ENV_VAR: &str = "MAZE_SEED";
+/// Command line flag the starting seed can be read from, takes precedence over [`SEED_ENV_VAR`].
+#[cfg(feature = "game")]
+const SEED_ARG: &str = "--seed";
+
+/// Drives all maze generation, the same seed always produces the same maze.
+#[cfg_attr(feature = "game", derive(Resource))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MazeSeed(pub u64);
+
+impl MazeSeed {
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+
+    /// Reads the seed from `--seed <seed>` or the `MAZE_SEED` environment variable,
+    /// falling back to a random seed.
+    #[cfg(feature = "game")]
+    pub fn from_env() -> Self {
+        let mut args = std::env::args().skip_while(|arg| arg != SEED_ARG).skip(1);
+        let seed = args.next().map(|seed| (SEED_ARG, seed)).or_else(|| {
+            std::env::var(SEED_ENV_VAR)
+                .ok()
+                .map(|seed| (SEED_ENV_VAR, seed))
+        });
+
+        match seed {
+            Some((source, seed)) => match seed.trim().parse() {
+                Ok(seed) => Self(seed),
+                Err(e) => {
+                    warn!("invalid seed {seed:?} from {source}: {e}");
+                    Self::random()
+                }
+            },
+            None => Self::random(),
+        }
+    }
+
+    /// A deterministic rng that is stable across platforms and `rand` versions.
+    pub fn rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.0)
+    }
+}
+
+impl fmt::Display for MazeSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,78 @@
+use glam::{IVec2, UVec2, Vec2};
+
+use super::{grid::MazeGrid, topology::Topology};
+
+/// Nudges hex lines off the exact edges between tiles, so they round consistently.
+const HEX_NUDGE: Vec2 = Vec2::new(1e-3, 2e-3);
+
+/// Every tile within `radius` tiles of `from` that can be seen from it.
+///
+/// Walls block sight but are visible themselves, so the walls lining a corridor are seen.
+pub fn line_of_sight(grid: &MazeGrid, from: UVec2, radius: u32) -> Vec<UVec2> {
+    let from = from.as_ivec2();
+    let radius = radius as i32;
+    let mut visible = Vec::new();
+
+    for y in from.y - radius..=from.y + radius {
+        for x in from.x - radius..=from.x + radius {
+            let to = IVec2::new(x, y);
+            if x < 0 || y < 0 || !grid.contains(to.as_uvec2()) {
+                continue;
+            }
+
+            let in_range = match grid.topology() {
+                Topology::Square | Topology::Isometric => {
+                    (to - from).length_squared() <= radius * radius
+                }
+                Topology::Hex => grid.topology().distance(from, to) <= radius as u32,
+            };
+
+            if in_range && is_clear(grid, from, to) {
+                visible.push(to.as_uvec2());
+            }
+        }
+    }
+
+    visible
+}
+
+/// Whether every tile on the line between `from` and `to`, excluding `to`, is floor.
+fn is_clear(grid: &MazeGrid, from: IVec2, to: IVec2) -> bool {
+    if grid.topology() == Topology::Hex {
+        return is_clear_hex(grid, from, to);
+    }
+
+    let delta = (to - from).abs();
+    let step = (to - from).signum();
+    let mut error = delta.x - delta.y;
+    let mut current = from;
+
+    while current != to {
+        if current != from && grid.is_wall(current.as_uvec2()) {
+            return false;
+        }
+
+        let e2 = error * 2;
+        if e2 > -delta.y {
+            error -= delta.y;
+            current.x += step.x;
+        }
+        if e2 < delta.x {
+            error += delta.x;
+            current.y += step.y;
+        }
+    }
+
+    true
+}
+
+/// [`is_clear`] for hex grids, stepping along the line one tile at a time.
+fn is_clear_hex(grid: &MazeGrid, from: IVec2, to: IVec2) -> bool {
+    let steps = grid.topology().distance(from, to);
+    let (a, b) = (from.as_vec2() + HEX_NUDGE, to.as_vec2() + HEX_NUDGE);
+
+    (1..steps).all(|step| {
+        let pos = grid.topology().round(a.lerp(b, step as f32 / steps as f32));
+        pos.x >= 0 && pos.y >= 0 && !grid.is_wall(pos.as_uvec2())
+    })
+}
@@ -0,0 +1,240 @@
+//! Path finding over the expanded tiles of a [`MazeGrid`].
+//!
+//! Every solver moves between neighboring floor tiles, as given by the grid's topology.
+
+use std::{cmp::Reverse, collections::BinaryHeap, collections::VecDeque};
+
+use glam::UVec2;
+
+use super::grid::{MazeGrid, TileType};
+
+/// Distance in tiles from a source tile to every reachable floor tile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceField {
+    width: u32,
+    source: UVec2,
+    distances: Vec<Option<u32>>,
+}
+
+impl DistanceField {
+    pub fn source(&self) -> UVec2 {
+        self.source
+    }
+
+    /// `None` if `pos` is a wall, outside the maze or unreachable from the source.
+    pub fn get(&self, pos: UVec2) -> Option<u32> {
+        if pos.x >= self.width {
+            return None;
+        }
+
+        self.distances
+            .get((pos.y * self.width + pos.x) as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// The furthest reachable tile and its distance.
+    pub fn furthest(&self) -> Option<(UVec2, u32)> {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.map(|d| (i, d)))
+            .max_by_key(|(_, d)| *d)
+            .map(|(i, d)| (UVec2::new(i as u32 % self.width, i as u32 / self.width), d))
+    }
+
+    /// Shortest path from the source to `to` by walking back down the distances.
+    pub fn path_to(&self, grid: &MazeGrid, to: UVec2) -> Option<Vec<UVec2>> {
+        let mut distance = self.get(to)?;
+        let mut path = vec![to];
+        let mut current = to;
+
+        while distance > 0 {
+            current = grid
+                .floor_neighbors(current)
+                .find(|n| self.get(*n) == Some(distance - 1))?;
+            distance -= 1;
+            path.push(current);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from `from` to every reachable floor tile.
+pub fn distance_field(grid: &MazeGrid, from: UVec2) -> DistanceField {
+    let mut distances = vec![None; grid.tiles().len()];
+    let mut queue = VecDeque::new();
+
+    if grid.is_floor(from) {
+        distances[grid.index(from)] = Some(0);
+        queue.push_back(from);
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[grid.index(current)].unwrap_or_default();
+        for neighbor in grid.floor_neighbors(current) {
+            let index = grid.index(neighbor);
+            if distances[index].is_none() {
+                distances[index] = Some(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    DistanceField {
+        width: grid.width(),
+        source: from,
+        distances,
+    }
+}
+
+/// Shortest path from `from` to `to` using breadth first search, both ends included.
+pub fn bfs(grid: &MazeGrid, from: UVec2, to: UVec2) -> Option<Vec<UVec2>> {
+    distance_field(grid, from).path_to(grid, to)
+}
+
+/// Shortest path from `from` to `to` using A* with the topology's step distance as the
+/// heuristic, both ends included.
+pub fn astar(grid: &MazeGrid, from: UVec2, to: UVec2) -> Option<Vec<UVec2>> {
+    if !grid.is_floor(from) || !grid.is_floor(to) {
+        return None;
+    }
+
+    let heuristic = |pos: UVec2| grid.topology().distance(pos.as_ivec2(), to.as_ivec2());
+
+    let mut costs = vec![u32::MAX; grid.tiles().len()];
+    let mut came_from = vec![None; grid.tiles().len()];
+    let mut open = BinaryHeap::new();
+
+    costs[grid.index(from)] = 0;
+    open.push(Reverse((heuristic(from), from.x, from.y)));
+
+    while let Some(Reverse((_, x, y))) = open.pop() {
+        let current = UVec2::new(x, y);
+        if current == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while let Some(previous) = came_from[grid.index(current)] {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let cost = costs[grid.index(current)] + 1;
+        for neighbor in grid.floor_neighbors(current) {
+            let index = grid.index(neighbor);
+            if cost < costs[index] {
+                costs[index] = cost;
+                came_from[index] = Some(current);
+                open.push(Reverse((
+                    cost + heuristic(neighbor),
+                    neighbor.x,
+                    neighbor.y,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+/// Repeatedly fills in dead ends until only tiles that lie on a route between `from`
+/// and `to` are left, returning those tiles.
+///
+/// In a maze without loops this is exactly the solution, in a braided maze every
+/// loop the solution could take is kept. Hex corridors meeting at a sharp corner make a
+/// loop of three tiles, so branches leading to one are kept too.
+pub fn dead_end_fill(grid: &MazeGrid, from: UVec2, to: UVec2) -> Vec<UVec2> {
+    let mut filled = grid.clone();
+    let is_dead_end = |grid: &MazeGrid, pos: UVec2| {
+        pos != from && pos != to && grid.floor_neighbors(pos).count() <= 1
+    };
+
+    let mut stack = grid
+        .iter()
+        .filter(|(pos, tile)| *tile == TileType::Floor && is_dead_end(grid, *pos))
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+
+    while let Some(pos) = stack.pop() {
+        if !filled.is_floor(pos) || !is_dead_end(&filled, pos) {
+            continue;
+        }
+
+        filled.set(pos, TileType::Wall);
+        stack.extend(
+            filled
+                .floor_neighbors(pos)
+                .filter(|n| is_dead_end(&filled, *n)),
+        );
+    }
+
+    // Anything disconnected from both ends is left over as well.
+    let reachable = distance_field(&filled, from);
+    filled
+        .iter()
+        .filter(|(pos, tile)| *tile == TileType::Floor && reachable.get(*pos).is_some())
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::maze::{generate, MazeAlgorithm, MazeConfig, MazeSeed, Topology};
+
+    #[test]
+    fn every_maze_is_solvable() {
+        for (topology, algorithm) in Topology::ALL
+            .into_iter()
+            .flat_map(|topology| MazeAlgorithm::ALL.map(|algorithm| (topology, algorithm)))
+        {
+            for braid in [0, 50] {
+                for seed in 0..8 {
+                    let config = MazeConfig {
+                        algorithm,
+                        braid,
+                        topology,
+                        ..Default::default()
+                    };
+                    let grid = generate(&config, &mut MazeSeed(seed).rng());
+                    let (start, end) = (grid.start(), grid.end());
+                    let name = format!("{topology} {algorithm} seed {seed} braid {braid}");
+
+                    let bfs =
+                        bfs(&grid, start, end).unwrap_or_else(|| panic!("{name}: no bfs path"));
+                    let astar =
+                        astar(&grid, start, end).unwrap_or_else(|| panic!("{name}: no astar path"));
+                    assert_eq!(bfs.first(), Some(&start), "{name}");
+                    assert_eq!(bfs.last(), Some(&end), "{name}");
+                    assert_eq!(astar.first(), Some(&start), "{name}");
+                    assert_eq!(astar.last(), Some(&end), "{name}");
+                    assert_eq!(bfs.len(), astar.len(), "{name}: astar is not shortest");
+
+                    // Without loops the only tiles left are the path itself, with loops
+                    // whole cycles can survive around it.
+                    let filled = dead_end_fill(&grid, start, end)
+                        .into_iter()
+                        .collect::<HashSet<_>>();
+                    let path = bfs.into_iter().collect::<HashSet<_>>();
+                    // Hex corridors meeting at a sharp corner touch, which leaves loops of
+                    // three tiles that dead end filling can't get past.
+                    if braid == 0 && topology != Topology::Hex {
+                        assert_eq!(filled, path, "{name}: dead end fill left another path");
+                    } else {
+                        assert!(
+                            filled.is_superset(&path),
+                            "{name}: dead end fill cut the path"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Generating and spawning the maze as a tilemap when a level loads.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::{
+    colliders, export, floors, fog, goal, import,
+    tileset::{TileSprite, Tileset},
+    CustomMaze, Goal, MazeAsset, MazeConfig, MazeFloor, MazeFloors, MazeGrid, MazeSeed, TileType,
+    Topology,
+};
+use crate::state::AppState;
+
+pub struct MazePlugin;
+
+impl Plugin for MazePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            TilemapPlugin,
+            goal::GoalPlugin,
+            fog::FogPlugin,
+            import::ImportPlugin,
+            export::ExportPlugin,
+            floors::FloorsPlugin,
+        ))
+        .init_resource::<MazeConfig>()
+        .insert_resource(MazeSeed::from_env())
+        .add_systems(
+            OnEnter(AppState::Loading),
+            (
+                (despawn_tileset, generate_maze, spawn_tileset)
+                    .chain()
+                    .in_set(SpawnMaze),
+                goal::reset_level_timer,
+            ),
+        )
+        .add_systems(OnEnter(AppState::Menu), despawn_tileset);
+    }
+}
+
+/// Generates and spawns the maze on entering [`AppState::Loading`], anything that
+/// relies on [`CurrentMaze`] there should run after this.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpawnMaze;
+
+/// The floor of the maze the player is on, see [`MazeFloors`].
+#[derive(Resource, Deref)]
+pub struct CurrentMaze(pub MazeGrid);
+
+/// Uses the [`CustomMaze`] once it has loaded, otherwise generates a maze from the seed.
+fn generate_maze(
+    mut commands: Commands,
+    config: Res<MazeConfig>,
+    seed: Res<MazeSeed>,
+    custom: Res<CustomMaze>,
+    assets: Res<Assets<MazeAsset>>,
+) {
+    let floors = match custom.get(&assets) {
+        Some(maze) => {
+            info!("using custom maze");
+            MazeFloors::single(maze.grid.clone())
+        }
+        None => {
+            info!("generating {:?} maze with seed {}", config.algorithm, *seed);
+            MazeFloors::generate(&config, &mut seed.rng())
+        }
+    };
+    commands.insert_resource(CurrentMaze(floors[0].clone()));
+    commands.insert_resource(floors);
+}
+
+fn spawn_tileset(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    config: Res<MazeConfig>,
+    floors: Res<MazeFloors>,
+    custom: Res<CustomMaze>,
+    assets: Res<Assets<MazeAsset>>,
+) {
+    // Custom mazes are always square, so go by the maze rather than the config.
+    let topology = floors[0].topology();
+    let tileset = Tileset::new(topology, config.tile_size, &asset_server, &mut images);
+    // Only custom mazes have items, and they only ever have one floor.
+    let items = custom
+        .get(&assets)
+        .map(|maze| maze.items.as_slice())
+        .unwrap_or_default();
+
+    for (floor, maze) in floors.iter().enumerate() {
+        let map_size = TilemapSize {
+            x: maze.width(),
+            y: maze.height(),
+        };
+        let tilemap_entity = commands.spawn_empty().id();
+
+        // Spawn the elements of the tilemap.
+        // Alternatively, you can use helpers::filling::fill_tilemap.
+        let mut tile_storage = TileStorage::empty(map_size);
+        for (pos, tile_type) in maze.iter() {
+            let tile_pos = TilePos { x: pos.x, y: pos.y };
+            let sprite = if floors.stairs(floor, pos).is_some() {
+                TileSprite::Stairs
+            } else if pos == maze.start() {
+                TileSprite::Start
+            } else if pos == maze.end() {
+                TileSprite::Goal
+            } else if floor == 0 && items.iter().any(|item| item.pos == pos) {
+                TileSprite::Item
+            } else if tile_type == TileType::Wall {
+                TileSprite::Wall
+            } else {
+                TileSprite::Floor
+            };
+            let tile = TileBundle {
+                // Hidden until the player sees it, see `fog`.
+                visible: TileVisible(false),
+                position: tile_pos,
+                tilemap_id: TilemapId(tilemap_entity),
+                texture_index: tileset.index(sprite),
+                ..Default::default()
+            };
+
+            let tile_entity = commands.spawn(tile);
+            tile_storage.set(&tile_pos, tile_entity.id());
+        }
+
+        for (center, collider) in colliders::wall_colliders(maze, config.tile_size) {
+            commands.spawn((
+                TileMapWall,
+                MazeFloor(floor),
+                RigidBody::Static,
+                collider,
+                floors::floor_layers(floor),
+                TransformBundle::from_transform(Transform::from_translation(center.extend(0.))),
+            ));
+        }
+
+        commands.entity(tilemap_entity).insert((
+            MazeFloor(floor),
+            TilemapBundle {
+                grid_size: tileset.grid_size,
+                map_type: tileset.map_type,
+                size: map_size,
+                storage: tile_storage,
+                texture: TilemapTexture::Single(tileset.texture.clone()),
+                tile_size: tileset.tile_size,
+                transform: get_tilemap_center_transform(
+                    &map_size,
+                    &tileset.grid_size,
+                    &tileset.map_type,
+                    floor as f32,
+                ),
+                visibility: if floor == floors.current() {
+                    Visibility::Visible
+                } else {
+                    Visibility::Hidden
+                },
+                ..Default::default()
+            },
+        ));
+    }
+
+    let top = &floors[floors.top()];
+    let goal_width = goal_width(top) as f32;
+    let goal_collider = match topology {
+        Topology::Square => {
+            Collider::rectangle(goal_width * config.tile_size, goal_width * config.tile_size)
+        }
+        // Open tiles around the end don't make a square on screen, so cover the middle of them.
+        _ => {
+            Collider::circle(goal_width * topology.grid_size(config.tile_size).min_element() * 0.5)
+        }
+    };
+    commands.spawn((
+        Goal,
+        MazeFloor(floors.top()),
+        RigidBody::Static,
+        Sensor,
+        goal_collider,
+        floors::floor_layers(floors.top()),
+        TransformBundle::from_transform(Transform::from_translation(
+            top.tile_to_world(top.end(), config.tile_size).extend(0.),
+        )),
+    ));
+}
+
+/// Width of the largest open square centered on the end of `maze`, so the goal covers
+/// the whole corridor whether the maze was generated or imported.
+fn goal_width(maze: &MazeGrid) -> u32 {
+    let end = maze.end();
+    let mut radius = 0;
+
+    loop {
+        let r = radius + 1;
+        let (Some(min_x), Some(min_y)) = (end.x.checked_sub(r), end.y.checked_sub(r)) else {
+            break;
+        };
+        let open = (min_y..=end.y + r)
+            .all(|y| (min_x..=end.x + r).all(|x| maze.is_floor(UVec2::new(x, y))));
+        if !open {
+            break;
+        }
+        radius = r;
+    }
+
+    radius * 2 + 1
+}
+
+#[derive(Component)]
+struct TileMapWall;
+
+fn despawn_tileset(
+    mut commands: Commands,
+    tilemaps: Query<(Entity, &TileStorage)>,
+    walls: Query<Entity, With<TileMapWall>>,
+    goals: Query<Entity, With<Goal>>,
+) {
+    for (entity, storage) in tilemaps.iter() {
+        for tile in storage.iter().flatten() {
+            commands.entity(*tile).despawn();
+        }
+        commands.entity(entity).despawn();
+    }
+
+    for entity in walls.iter().chain(goals.iter()) {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use bevy_ecs_tilemap::prelude::*;
+
+use super::topology::Topology;
+
+/// How far in from the edge of a shaped tile its darker border goes, in pixels.
+const BORDER: f32 = 1.5;
+
+/// What a single tile is drawn as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileSprite {
+    Wall,
+    Floor,
+    Start,
+    Goal,
+    Stairs,
+    /// An item placed in an imported maze, see [`super::import::MazeItem`].
+    Item,
+}
+
+impl TileSprite {
+    const ALL: [TileSprite; 6] = [
+        Self::Wall,
+        Self::Floor,
+        Self::Start,
+        Self::Goal,
+        Self::Stairs,
+        Self::Item,
+    ];
+
+    /// Where the sprite is in `tileset.png`.
+    fn square_index(self) -> TileTextureIndex {
+        match self {
+            Self::Wall => TileTextureIndex(17),
+            Self::Floor => TileTextureIndex(92),
+            Self::Start => TileTextureIndex(147),
+            Self::Goal => TileTextureIndex(146),
+            Self::Stairs => TileTextureIndex(140),
+            Self::Item => TileTextureIndex(205),
+        }
+    }
+
+    /// Flat color for shaped tiles, which `tileset.png` has no art for.
+    fn color(self) -> [u8; 4] {
+        match self {
+            Self::Wall => [52, 52, 72, 255],
+            Self::Floor => [150, 145, 135, 255],
+            Self::Start => [70, 170, 90, 255],
+            Self::Goal => [200, 70, 60, 255],
+            Self::Stairs => [170, 120, 60, 255],
+            Self::Item => [220, 190, 60, 255],
+        }
+    }
+}
+
+/// The texture and layout of a maze's tilemap.
+pub struct Tileset {
+    topology: Topology,
+    pub texture: Handle<Image>,
+    pub tile_size: TilemapTileSize,
+    pub grid_size: TilemapGridSize,
+    pub map_type: TilemapType,
+}
+
+impl Tileset {
+    /// Square mazes use `tileset.png`, the other topologies get plain shaped tiles drawn
+    /// on the fly.
+    pub fn new(
+        topology: Topology,
+        tile_size: f32,
+        asset_server: &AssetServer,
+        images: &mut Assets<Image>,
+    ) -> Self {
+        let size = topology.grid_size(tile_size);
+        let texture = match topology {
+            Topology::Square => asset_server.load("tileset.png"),
+            _ => images.add(shaped_tiles(topology, tile_size)),
+        };
+
+        Self {
+            topology,
+            texture,
+            tile_size: TilemapTileSize {
+                x: size.x,
+                y: size.y,
+            },
+            grid_size: TilemapGridSize {
+                x: size.x,
+                y: size.y,
+            },
+            map_type: match topology {
+                Topology::Square => TilemapType::Square,
+                Topology::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
+                Topology::Hex => TilemapType::Hexagon(HexCoordSystem::Row),
+            },
+        }
+    }
+
+    pub fn index(&self, sprite: TileSprite) -> TileTextureIndex {
+        match self.topology {
+            Topology::Square => sprite.square_index(),
+            _ => TileTextureIndex(sprite as u32),
+        }
+    }
+}
+
+/// A strip of every [`TileSprite`] in the shape of a `topology` tile, with a darker border.
+fn shaped_tiles(topology: Topology, tile_size: f32) -> Image {
+    let size = topology.grid_size(tile_size);
+    let (width, height) = (size.x as u32, size.y as u32);
+    let outline = topology.outline(tile_size);
+
+    // How far inside the outline a pixel is, negative outside of it.
+    let depth = |x: u32, y: u32| {
+        let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - size * 0.5;
+        outline
+            .iter()
+            .zip(outline.iter().cycle().skip(1))
+            .map(|(a, b)| (*b - *a).normalize().perp_dot(p - *a))
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    let mut data = Vec::with_capacity((width * height * 4) as usize * TileSprite::ALL.len());
+    for y in (0..height).rev() {
+        for sprite in TileSprite::ALL {
+            for x in 0..width {
+                let depth = depth(x, y);
+                let [r, g, b, a] = sprite.color();
+                data.extend(if depth < 0. {
+                    [0, 0, 0, 0]
+                } else if depth < BORDER {
+                    [r / 2, g / 2, b / 2, a]
+                } else {
+                    [r, g, b, a]
+                });
+            }
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: width * TileSprite::ALL.len() as u32,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
@@ -0,0 +1,230 @@
+use std::{fmt, str::FromStr};
+
+use glam::{IVec2, Vec2};
+use serde::{Deserialize, Serialize};
+
+/// Height of a hex tile relative to its width, a little squashed from a regular hexagon
+/// so the tiles stay a whole number of pixels.
+const HEX_HEIGHT: f32 = 1.125;
+
+const SQUARE_OFFSETS: [IVec2; 4] = [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y];
+const HEX_OFFSETS: [IVec2; 6] = [
+    IVec2::X,
+    IVec2::Y,
+    IVec2::NEG_X,
+    IVec2::NEG_Y,
+    IVec2::new(-1, 1),
+    IVec2::new(1, -1),
+];
+
+/// How tiles are shaped and which tiles are next to each other.
+///
+/// Positions are always a column and row counted from the bottom left, the topology
+/// decides where on screen that is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Topology {
+    /// Square tiles with four neighbors.
+    #[default]
+    Square,
+    /// Square tiles seen from an angle, drawn as diamonds twice as wide as they are tall.
+    /// Columns run down to the right and rows up to the right.
+    Isometric,
+    /// Pointy topped hexagons with six neighbors, in axial coordinates. Every row is
+    /// shifted half a tile right of the one below, so the maze is a rhombus.
+    Hex,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 3] = [Self::Square, Self::Isometric, Self::Hex];
+
+    /// Short name used on the command line and in exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Square => "square",
+            Self::Isometric => "isometric",
+            Self::Hex => "hex",
+        }
+    }
+
+    /// Offsets from a tile to each of its neighbors.
+    pub fn offsets(self) -> &'static [IVec2] {
+        match self {
+            Self::Square | Self::Isometric => &SQUARE_OFFSETS,
+            Self::Hex => &HEX_OFFSETS,
+        }
+    }
+
+    /// Steps between two tiles, moving only between neighbors.
+    pub fn distance(self, a: IVec2, b: IVec2) -> u32 {
+        let d = (a - b).abs();
+        match self {
+            Self::Square | Self::Isometric => (d.x + d.y) as u32,
+            Self::Hex => ((d.x + d.y + (a - b).element_sum().abs()) / 2) as u32,
+        }
+    }
+
+    /// Distance between tile centres in world units, for tiles `tile_size` wide.
+    pub fn grid_size(self, tile_size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::splat(tile_size),
+            Self::Isometric => Vec2::new(tile_size * 2., tile_size),
+            Self::Hex => Vec2::new(tile_size, (tile_size * HEX_HEIGHT).round()),
+        }
+    }
+
+    /// The world position of `pos`, which may be fractional, with tile `(0, 0)` at the
+    /// origin. Matches how `bevy_ecs_tilemap` lays out each topology.
+    pub fn project(self, pos: Vec2, tile_size: f32) -> Vec2 {
+        let grid = self.grid_size(tile_size);
+        match self {
+            Self::Square => pos * grid,
+            Self::Isometric => Vec2::new(pos.x + pos.y, pos.y - pos.x) * 0.5 * grid,
+            Self::Hex => Vec2::new(pos.x + pos.y * 0.5, pos.y * 0.75) * grid,
+        }
+    }
+
+    /// The inverse of [`Self::project`].
+    pub fn unproject(self, world: Vec2, tile_size: f32) -> Vec2 {
+        let pos = world / self.grid_size(tile_size);
+        match self {
+            Self::Square => pos,
+            Self::Isometric => Vec2::new(pos.x - pos.y, pos.x + pos.y),
+            Self::Hex => {
+                let y = pos.y / 0.75;
+                Vec2::new(pos.x - y * 0.5, y)
+            }
+        }
+    }
+
+    /// The tile a fractional position from [`Self::unproject`] lies in.
+    pub fn round(self, pos: Vec2) -> IVec2 {
+        match self {
+            Self::Square | Self::Isometric => pos.round().as_ivec2(),
+            Self::Hex => {
+                // Round in cube coordinates, fixing up whichever axis rounded the furthest.
+                let z = -pos.x - pos.y;
+                let rounded = pos.round();
+                let rz = z.round();
+                let (dx, dy, dz) = (
+                    (rounded.x - pos.x).abs(),
+                    (rounded.y - pos.y).abs(),
+                    (rz - z).abs(),
+                );
+
+                if dx > dy && dx > dz {
+                    IVec2::new((-rounded.y - rz) as i32, rounded.y as i32)
+                } else if dy > dz {
+                    IVec2::new(rounded.x as i32, (-rounded.x - rz) as i32)
+                } else {
+                    rounded.as_ivec2()
+                }
+            }
+        }
+    }
+
+    /// The corners of a tile around its centre, in world units and counter clockwise.
+    pub fn outline(self, tile_size: f32) -> Vec<Vec2> {
+        let half = self.grid_size(tile_size) * 0.5;
+        match self {
+            Self::Square => vec![
+                Vec2::new(-half.x, -half.y),
+                Vec2::new(half.x, -half.y),
+                Vec2::new(half.x, half.y),
+                Vec2::new(-half.x, half.y),
+            ],
+            Self::Isometric => vec![
+                Vec2::new(0., -half.y),
+                Vec2::new(half.x, 0.),
+                Vec2::new(0., half.y),
+                Vec2::new(-half.x, 0.),
+            ],
+            Self::Hex => vec![
+                Vec2::new(0., -half.y),
+                Vec2::new(half.x, -half.y * 0.5),
+                Vec2::new(half.x, half.y * 0.5),
+                Vec2::new(0., half.y),
+                Vec2::new(-half.x, half.y * 0.5),
+                Vec2::new(-half.x, -half.y * 0.5),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|topology| topology.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name).join(", ");
+                format!("unknown topology {s:?}, expected one of {names}")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE_SIZE: f32 = 16.;
+
+    fn tiles() -> impl Iterator<Item = IVec2> {
+        (-8..8).flat_map(|y| (-8..8).map(move |x| IVec2::new(x, y)))
+    }
+
+    #[test]
+    fn unproject_undoes_project() {
+        for topology in Topology::ALL {
+            for tile in tiles() {
+                let pos = tile.as_vec2() + Vec2::new(0.25, -0.4);
+                let world = topology.project(pos, TILE_SIZE);
+                let back = topology.unproject(world, TILE_SIZE);
+                assert!(
+                    back.abs_diff_eq(pos, 1e-4),
+                    "{topology} {pos} came back as {back}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn points_round_to_the_tile_they_are_in() {
+        for topology in Topology::ALL {
+            for tile in tiles() {
+                let center = topology.project(tile.as_vec2(), TILE_SIZE);
+                assert_eq!(
+                    topology.round(topology.unproject(center, TILE_SIZE)),
+                    tile,
+                    "{topology} centre of {tile}"
+                );
+
+                // Just inside every corner of the tile.
+                for corner in topology.outline(TILE_SIZE) {
+                    let world = center + corner * 0.9;
+                    assert_eq!(
+                        topology.round(topology.unproject(world, TILE_SIZE)),
+                        tile,
+                        "{topology} corner {corner} of {tile}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_are_one_step_away() {
+        for topology in Topology::ALL {
+            for offset in topology.offsets() {
+                assert_eq!(topology.distance(IVec2::ZERO, *offset), 1, "{topology}");
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::{
+    maze::{CurrentMaze, FogOfWar, MazeGrid, SpawnMaze},
+    state::AppState,
+};
+
+/// Size of the minimap on screen in pixels.
+const MINIMAP_SIZE: f32 = 240.;
+/// Tile size the maze is laid out with on the minimap, only its proportions matter.
+const TILE_SIZE: f32 = 16.;
+/// Minimap pixels across a tile.
+const PIXELS_PER_TILE: f32 = 2.;
+
+const OUTSIDE: [u8; 4] = [0, 0, 0, 0];
+const UNEXPLORED: [u8; 4] = [0, 0, 0, 160];
+const EXPLORED_WALL: [u8; 4] = [40, 40, 60, 220];
+const EXPLORED_FLOOR: [u8; 4] = [120, 120, 140, 220];
+const VISIBLE_FLOOR: [u8; 4] = [220, 220, 230, 230];
+const PLAYER: [u8; 4] = [230, 60, 60, 255];
+const GOAL: [u8; 4] = [80, 220, 100, 255];
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MinimapSettings>()
+            .add_systems(
+                OnEnter(AppState::Loading),
+                (despawn_minimap, spawn_minimap).chain().after(SpawnMaze),
+            )
+            .add_systems(OnEnter(AppState::Menu), despawn_minimap)
+            .add_systems(Update, draw_minimap);
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct MinimapSettings {
+    /// Shows the exit even before it has been explored.
+    pub show_goal: bool,
+}
+
+impl Default for MinimapSettings {
+    fn default() -> Self {
+        Self { show_goal: true }
+    }
+}
+
+/// The UI node showing the minimap image, with the tiles laid out the way the maze is
+/// on screen.
+#[derive(Component)]
+struct Minimap {
+    image: Handle<Image>,
+    /// The tile under every pixel, row by row from the top left.
+    pixels: Vec<Option<UVec2>>,
+}
+
+fn spawn_minimap(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    maze: Res<CurrentMaze>,
+) {
+    let (size, pixels) = layout(&maze);
+
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &OUTSIDE,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+
+    // Keep the aspect ratio of the maze, with the longest side MINIMAP_SIZE.
+    let scale = MINIMAP_SIZE / size.max_element() as f32;
+
+    commands.spawn((
+        Minimap {
+            image: image.clone(),
+            pixels,
+        },
+        ImageBundle {
+            image: UiImage::new(image),
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(8.),
+                top: Val::Px(8.),
+                width: Val::Px(size.x as f32 * scale),
+                height: Val::Px(size.y as f32 * scale),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+}
+
+/// Lays the tiles of `maze` out the way they are on screen, returning the size of the image
+/// and the tile under each of its pixels.
+fn layout(maze: &MazeGrid) -> (UVec2, Vec<Option<UVec2>>) {
+    let topology = maze.topology();
+    let outline = topology.outline(TILE_SIZE);
+    let (min, max) = maze
+        .iter()
+        .map(|(pos, _)| topology.project(pos.as_vec2(), TILE_SIZE))
+        .flat_map(|center| outline.iter().map(move |corner| center + *corner))
+        .fold((Vec2::MAX, Vec2::MIN), |(min, max), corner| {
+            (min.min(corner), max.max(corner))
+        });
+
+    let pixel = TILE_SIZE / PIXELS_PER_TILE;
+    let size = ((max - min) / pixel).ceil().as_uvec2();
+    let pixels = (0..size.y)
+        .flat_map(|row| (0..size.x).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            // Images start at the top left, the world goes up.
+            let world = Vec2::new(
+                min.x + (column as f32 + 0.5) * pixel,
+                max.y - (row as f32 + 0.5) * pixel,
+            );
+            let tile = topology.round(topology.unproject(world, TILE_SIZE));
+            (tile.cmpge(IVec2::ZERO).all() && maze.contains(tile.as_uvec2()))
+                .then(|| tile.as_uvec2())
+        })
+        .collect();
+
+    (size, pixels)
+}
+
+fn despawn_minimap(mut commands: Commands, minimaps: Query<Entity, With<Minimap>>) {
+    for entity in minimaps.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Redraws the minimap straight from the maze grid whenever more of it is seen.
+fn draw_minimap(
+    minimaps: Query<&Minimap>,
+    mut images: ResMut<Assets<Image>>,
+    fog: Res<FogOfWar>,
+    maze: Option<Res<CurrentMaze>>,
+    settings: Res<MinimapSettings>,
+) {
+    let Some(maze) = maze else {
+        return;
+    };
+
+    if !fog.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let mut colors = maze
+        .iter()
+        .map(|(pos, _)| {
+            if !fog.is_explored(pos) {
+                UNEXPLORED
+            } else if maze.is_wall(pos) {
+                EXPLORED_WALL
+            } else {
+                EXPLORED_FLOOR
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for pos in fog.visible() {
+        if maze.is_floor(*pos) {
+            colors[maze.index(*pos)] = VISIBLE_FLOOR;
+        }
+    }
+
+    if settings.show_goal || fog.is_explored(maze.end()) {
+        colors[maze.index(maze.end())] = GOAL;
+    }
+
+    if let Some(player) = fog.origin().filter(|pos| maze.contains(*pos)) {
+        colors[maze.index(player)] = PLAYER;
+    }
+
+    for minimap in minimaps.iter() {
+        let Some(image) = images.get_mut(&minimap.image) else {
+            continue;
+        };
+
+        for (pixel, tile) in image.data.chunks_exact_mut(4).zip(&minimap.pixels) {
+            let color = tile.map_or(OUTSIDE, |tile| colors[maze.index(tile)]);
+            pixel.copy_from_slice(&color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::Topology;
+
+    #[test]
+    fn every_tile_shows_up() {
+        for topology in Topology::ALL {
+            let maze = MazeGrid::with_topology(12, 8, topology);
+            let (size, pixels) = layout(&maze);
+            assert_eq!(pixels.len(), (size.x * size.y) as usize);
+
+            for (pos, _) in maze.iter() {
+                assert!(
+                    pixels.contains(&Some(pos)),
+                    "{topology} tile {pos} has no pixels"
+                );
+            }
+        }
+    }
+}
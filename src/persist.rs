@@ -0,0 +1,29 @@
+//! Files kept in the user's data directory between runs.
+
+use std::{fs, io, path::PathBuf};
+
+/// Directory inside the platform data directory everything is kept in.
+const DATA_DIR: &str = "maze-lite";
+
+/// Where the file `name` is kept, `None` if the platform has no data directory.
+pub fn path(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR).join(name))
+}
+
+/// Replaces the file `name` with `contents`.
+///
+/// The contents are written to a temporary file first and then renamed over the old one,
+/// so a crash never leaves a half written file behind.
+pub fn write_atomic(name: &str, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path =
+        path(name).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut tmp = path.clone().into_os_string();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(tmp, path)
+}
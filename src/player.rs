@@ -2,8 +2,19 @@ use avian2d::schedule::PostProcessCollisions;
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
+use crate::{
+    maze::{floors, CurrentMaze, MazeConfig, MazeGrid, SpawnMaze},
+    state::AppState,
+};
+
+mod hint;
 mod input;
 mod movement;
+mod sprite;
+
+pub use hint::Hint;
+
+pub use sprite::DEATH_CLIP;
 
 pub struct PlayerPlugin;
 
@@ -12,8 +23,14 @@ impl Plugin for PlayerPlugin {
         app.add_plugins((
             InputManagerPlugin::<input::PlayerAction>::default(),
             movement::CharacterControllerPlugin,
+            sprite::PlayerSpritePlugin,
+            hint::HintPlugin,
         ))
-        .add_systems(Startup, spawn_player)
+        .add_systems(
+            OnEnter(AppState::Loading),
+            (despawn_player, spawn_player).chain().after(SpawnMaze),
+        )
+        .add_systems(OnEnter(AppState::Menu), despawn_player)
         .add_systems(Update, (input::handle_actions,))
         .add_systems(PostProcessCollisions, follow_player);
     }
@@ -22,23 +39,37 @@ impl Plugin for PlayerPlugin {
 #[derive(Component)]
 pub struct Player;
 
-fn spawn_player(mut commands: Commands, server: Res<AssetServer>) {
-    let texture = server.load("textures/smile.png");
-
-    commands.spawn((
-        Player,
-        movement::CharacterControllerBundle::new(),
-        SpriteBundle {
-            transform: Transform::from_translation(Vec3::new(
-                -6.5 * 16. * 3. - 1.,
-                -6.5 * 16. * 3. - 1.,
-                100.,
-            )),
-            texture,
-            ..Default::default()
-        },
-        InputManagerBundle::with_map(input::PlayerAction::default_input_map()),
-    ));
+fn spawn_player(
+    mut commands: Commands,
+    sheets: Res<sprite::AdventurerSheets>,
+    config: Res<MazeConfig>,
+    maze: Res<CurrentMaze>,
+) {
+    commands
+        .spawn((
+            Player,
+            movement::CharacterControllerBundle::new(),
+            floors::player_layers(0),
+            SpatialBundle::from_transform(player_start(&config, &maze)),
+            sprite::Facing::default(),
+            sprite::AdventurerState::default(),
+            InputManagerBundle::with_map(input::PlayerAction::default_input_map()),
+        ))
+        .with_children(|parent| sprite::spawn_sprite(parent, &sheets));
+}
+
+/// Where the player spawns in `maze`.
+fn player_start(config: &MazeConfig, maze: &MazeGrid) -> Transform {
+    Transform::from_translation(
+        maze.tile_to_world(maze.start(), config.tile_size)
+            .extend(100.),
+    )
+}
+
+fn despawn_player(mut commands: Commands, players: Query<Entity, With<Player>>) {
+    for entity in players.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
 }
 
 fn follow_player(
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use super::{input::PlayerAction, Player};
+use crate::{
+    maze::{solve, CurrentMaze, MazeConfig},
+    state::AppState,
+    stats::RunStats,
+};
+
+/// How many tiles of the path to the exit a hint reveals.
+const HINT_TILES: usize = 12;
+const HINT_DURATION: Duration = Duration::from_secs(3);
+const HINT_COOLDOWN: Duration = Duration::from_secs(10);
+/// Added to the level time every time a hint is used.
+const HINT_PENALTY: Duration = Duration::from_secs(5);
+
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Hint>()
+            .add_systems(OnEnter(AppState::Loading), clear_hint)
+            .add_systems(
+                Update,
+                (request_hint, draw_hint)
+                    .chain()
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// The currently revealed part of the path to the exit.
+#[derive(Resource)]
+pub struct Hint {
+    pub path: Vec<UVec2>,
+    shown: Timer,
+    cooldown: Timer,
+}
+
+impl Default for Hint {
+    fn default() -> Self {
+        let mut shown = Timer::new(HINT_DURATION, TimerMode::Once);
+        shown.tick(HINT_DURATION);
+        let mut cooldown = Timer::new(HINT_COOLDOWN, TimerMode::Once);
+        cooldown.tick(HINT_COOLDOWN);
+
+        Self {
+            path: Vec::new(),
+            shown,
+            cooldown,
+        }
+    }
+}
+
+impl Hint {
+    /// Time left until another hint can be used.
+    pub fn cooldown_remaining(&self) -> Duration {
+        self.cooldown.remaining()
+    }
+}
+
+fn clear_hint(mut hint: ResMut<Hint>) {
+    *hint = Hint::default();
+}
+
+fn request_hint(
+    time: Res<Time>,
+    player: Query<(&ActionState<PlayerAction>, &Transform), With<Player>>,
+    config: Res<MazeConfig>,
+    maze: Res<CurrentMaze>,
+    mut hint: ResMut<Hint>,
+    mut stats: ResMut<RunStats>,
+) {
+    hint.cooldown.tick(time.delta());
+    hint.shown.tick(time.delta());
+
+    let Some((action, transform)) = player.iter().next() else {
+        return;
+    };
+
+    if !action.just_pressed(&PlayerAction::Hint) || !hint.cooldown.finished() {
+        return;
+    }
+
+    let Some(path) = maze
+        .world_to_tile(transform.translation.truncate(), config.tile_size)
+        .and_then(|tile| solve::astar(&maze, tile, maze.end()))
+    else {
+        return;
+    };
+
+    hint.path = path.into_iter().take(HINT_TILES + 1).collect();
+    hint.shown.reset();
+    hint.cooldown.reset();
+    stats.hints += 1;
+    stats.penalty += HINT_PENALTY;
+}
+
+fn draw_hint(
+    mut gizmos: Gizmos,
+    hint: Res<Hint>,
+    config: Res<MazeConfig>,
+    maze: Option<Res<CurrentMaze>>,
+) {
+    let Some(maze) = maze.filter(|_| !hint.shown.finished()) else {
+        return;
+    };
+
+    // Fade out over the last second.
+    let alpha = hint.shown.remaining_secs().min(1.);
+    gizmos.linestrip_2d(
+        hint.path
+            .iter()
+            .map(|tile| maze.tile_to_world(*tile, config.tile_size)),
+        Color::srgba(1., 0.85, 0.2, alpha),
+    );
+}
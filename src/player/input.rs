@@ -7,12 +7,14 @@ use super::Player;
 #[non_exhaustive]
 pub enum PlayerAction {
     Move,
+    Hint,
 }
 
 impl Actionlike for PlayerAction {
     fn input_control_kind(&self) -> InputControlKind {
         match self {
             PlayerAction::Move => InputControlKind::DualAxis,
+            PlayerAction::Hint => InputControlKind::Button,
         }
     }
 }
@@ -24,13 +26,15 @@ impl PlayerAction {
         // Default gamepad and keyboard input bindings
         input_map.insert_dual_axis(Self::Move, GamepadStick::LEFT);
         input_map.insert_dual_axis(Self::Move, KeyboardVirtualDPad::WASD);
+        input_map.insert(Self::Hint, GamepadButtonType::North);
+        input_map.insert(Self::Hint, KeyCode::KeyH);
 
         input_map
     }
 }
 
 pub fn handle_actions(query: Query<&ActionState<PlayerAction>, With<Player>>) {
-    let Some(action) = query.iter().next() else {
+    let Some(_action) = query.iter().next() else {
         return;
     };
 
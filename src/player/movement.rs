@@ -3,12 +3,13 @@ use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
 use super::{input::PlayerAction, Player};
+use crate::state::AppState;
 
 pub struct CharacterControllerPlugin;
 
 impl Plugin for CharacterControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, movement);
+        app.add_systems(Update, movement.run_if(in_state(AppState::Playing)));
         // .add_plugins(PhysicsDebugPlugin::default());
     }
 }
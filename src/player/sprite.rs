@@ -0,0 +1,287 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::Player;
+use crate::{
+    animated_sprites::{AnimationController, LoopMode, SpriteClip},
+    state::AppState,
+};
+
+/// Every adventurer sheet is a single row of 48x64 frames.
+const FRAME_SIZE: UVec2 = UVec2::new(48, 64);
+const FRAMES: u32 = 8;
+/// The adventurer is drawn at half size so it fits in a single tile corridor.
+const SCALE: f32 = 0.5;
+/// Moves the sprite up so the adventurer's feet line up with the collider.
+const FEET_OFFSET: f32 = 8. * SCALE;
+/// Below this speed the adventurer is idle.
+const WALK_THRESHOLD: f32 = 1.;
+
+/// Name of the adventurer's death clip, which holds its last frame once finished.
+pub const DEATH_CLIP: &str = "death";
+
+pub struct PlayerSpritePlugin;
+
+impl Plugin for PlayerSpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_sheets)
+            .add_systems(OnEnter(AppState::GameOver), die)
+            .add_systems(
+                Update,
+                (
+                    update_facing.run_if(in_state(AppState::Playing)),
+                    apply_animation,
+                    sync_death_shadow,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The six directions the adventurer sheets are drawn in. There are no straight left or
+/// right sheets, so moving sideways uses the diagonal down facing ones.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Facing {
+    Up,
+    RightUp,
+    RightDown,
+    #[default]
+    Down,
+    LeftDown,
+    LeftUp,
+}
+
+impl Facing {
+    const ALL: [Facing; 6] = [
+        Self::Up,
+        Self::RightUp,
+        Self::RightDown,
+        Self::Down,
+        Self::LeftDown,
+        Self::LeftUp,
+    ];
+
+    fn from_velocity(velocity: Vec2) -> Self {
+        let dir = velocity.normalize_or_zero();
+
+        if dir.x.abs() < 0.38 {
+            if dir.y > 0. {
+                Self::Up
+            } else {
+                Self::Down
+            }
+        } else if dir.x > 0. {
+            if dir.y > 0.38 {
+                Self::RightUp
+            } else {
+                Self::RightDown
+            }
+        } else if dir.y > 0.38 {
+            Self::LeftUp
+        } else {
+            Self::LeftDown
+        }
+    }
+
+    fn file_suffix(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::RightUp => "right_up",
+            Self::RightDown => "right_down",
+            Self::Down => "down",
+            Self::LeftDown => "left_down",
+            Self::LeftUp => "left_up",
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdventurerState {
+    #[default]
+    Idle,
+    Walk,
+    Death,
+}
+
+impl AdventurerState {
+    fn clip(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Walk => "walk",
+            Self::Death => DEATH_CLIP,
+        }
+    }
+}
+
+/// Every sheet has the same frames, only the texture changes between directions.
+fn controller() -> AnimationController {
+    AnimationController::new("idle", SpriteClip::new(0..FRAMES, 8.))
+        .with_clip("walk", SpriteClip::new(0..FRAMES, 12.))
+        .with_clip(
+            DEATH_CLIP,
+            SpriteClip::new(0..FRAMES, 10.).with_mode(LoopMode::HoldLast),
+        )
+}
+
+#[derive(Resource)]
+pub struct AdventurerSheets {
+    layout: Handle<TextureAtlasLayout>,
+    idle: Vec<Handle<Image>>,
+    walk: Vec<Handle<Image>>,
+    death: Vec<Handle<Image>>,
+    death_shadow: Vec<Handle<Image>>,
+    shadow: Handle<Image>,
+}
+
+impl AdventurerSheets {
+    fn body(&self, state: AdventurerState, facing: Facing) -> Handle<Image> {
+        let sheets = match state {
+            AdventurerState::Idle => &self.idle,
+            AdventurerState::Walk => &self.walk,
+            AdventurerState::Death => &self.death,
+        };
+
+        sheets[facing as usize].clone()
+    }
+}
+
+fn load_sheets(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let load = |folder: &str, name: &str| {
+        Facing::ALL
+            .iter()
+            .map(|facing| {
+                server.load(format!(
+                    "textures/adventurer/{folder}/{name}_{}.png",
+                    facing.file_suffix()
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    commands.insert_resource(AdventurerSheets {
+        layout: layouts.add(TextureAtlasLayout::from_grid(
+            FRAME_SIZE, FRAMES, 1, None, None,
+        )),
+        idle: load("Idle", "idle"),
+        walk: load("Walk", "walk"),
+        death: load("Death", "death_normal"),
+        death_shadow: load("Death Shadow", "death_normal"),
+        shadow: server.load("textures/adventurer/Shadow.png"),
+    });
+}
+
+#[derive(Component)]
+pub struct AdventurerBody;
+
+#[derive(Component)]
+pub struct AdventurerShadow;
+
+/// Spawns the adventurer and its shadow as children of the player.
+pub fn spawn_sprite(parent: &mut ChildBuilder, sheets: &AdventurerSheets) {
+    let transform = Transform::from_xyz(0., FEET_OFFSET, 0.).with_scale(Vec3::splat(SCALE));
+
+    parent.spawn((
+        AdventurerShadow,
+        SpriteBundle {
+            texture: sheets.shadow.clone(),
+            transform,
+            ..Default::default()
+        },
+    ));
+
+    parent.spawn((
+        AdventurerBody,
+        SpriteBundle {
+            texture: sheets.body(AdventurerState::Idle, Facing::Down),
+            transform: transform.with_translation(transform.translation + Vec3::Z * 0.1),
+            ..Default::default()
+        },
+        TextureAtlas::from(sheets.layout.clone()),
+        controller(),
+    ));
+}
+
+fn update_facing(
+    mut players: Query<(&LinearVelocity, &mut Facing, &mut AdventurerState), With<Player>>,
+) {
+    for (velocity, mut facing, mut state) in players.iter_mut() {
+        if velocity.length() > WALK_THRESHOLD {
+            facing.set_if_neq(Facing::from_velocity(velocity.0));
+            state.set_if_neq(AdventurerState::Walk);
+        } else {
+            state.set_if_neq(AdventurerState::Idle);
+        }
+    }
+}
+
+fn die(mut players: Query<&mut AdventurerState, With<Player>>) {
+    for mut state in players.iter_mut() {
+        *state = AdventurerState::Death;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_animation(
+    mut commands: Commands,
+    sheets: Res<AdventurerSheets>,
+    players: Query<(Ref<Facing>, Ref<AdventurerState>, &Children), With<Player>>,
+    mut bodies: Query<(&mut Handle<Image>, &mut AnimationController), With<AdventurerBody>>,
+    mut shadows: Query<
+        (Entity, &mut Handle<Image>),
+        (With<AdventurerShadow>, Without<AdventurerBody>),
+    >,
+) {
+    for (facing, state, children) in players.iter() {
+        if !facing.is_changed() && !state.is_changed() {
+            continue;
+        }
+
+        for child in children.iter() {
+            if let Ok((mut texture, mut controller)) = bodies.get_mut(*child) {
+                *texture = sheets.body(*state, *facing);
+                // Changing direction keeps the current frame so walking stays smooth.
+                controller.play(state.clip());
+            }
+
+            if let Ok((entity, mut texture)) = shadows.get_mut(*child) {
+                // The death animation has its own shadow that follows the falling body,
+                // see `sync_death_shadow`.
+                if *state == AdventurerState::Death {
+                    *texture = sheets.death_shadow[*facing as usize].clone();
+                    commands
+                        .entity(entity)
+                        .insert(TextureAtlas::from(sheets.layout.clone()));
+                } else if state.is_changed() {
+                    *texture = sheets.shadow.clone();
+                    commands.entity(entity).remove::<TextureAtlas>();
+                }
+            }
+        }
+    }
+}
+
+fn sync_death_shadow(
+    players: Query<&Children, With<Player>>,
+    bodies: Query<&TextureAtlas, With<AdventurerBody>>,
+    mut shadows: Query<&mut TextureAtlas, (With<AdventurerShadow>, Without<AdventurerBody>)>,
+) {
+    for children in players.iter() {
+        let Some(index) = children
+            .iter()
+            .find_map(|child| bodies.get(*child).ok())
+            .map(|atlas| atlas.index)
+        else {
+            continue;
+        };
+
+        for child in children.iter() {
+            if let Ok(mut atlas) = shadows.get_mut(*child) {
+                atlas.index = index;
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::{error::Error, fmt, io, ops::RangeInclusive};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    maze::{fog::SightRadius, metrics, CustomMaze, MazeAlgorithm, MazeConfig, MazeSeed, SpawnMaze},
+    save::PendingSave,
+    state::AppState,
+};
+
+/// Seeds tried to land a maze inside a level's difficulty band.
+const DIFFICULTY_ATTEMPTS: usize = 32;
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ProgressionCurve>()
+            .init_asset_loader::<ProgressionCurveLoader>()
+            .init_resource::<LevelProgression>()
+            .add_systems(OnEnter(AppState::Menu), reset_progression)
+            .add_systems(OnExit(AppState::LevelComplete), next_level)
+            .add_systems(
+                OnEnter(AppState::Loading),
+                apply_progression.before(SpawnMaze),
+            );
+    }
+}
+
+/// Which level of the session the player is on, each one configured by the
+/// [`ProgressionCurve`] in `assets/levels.progression.ron`.
+#[derive(Resource)]
+pub struct LevelProgression {
+    /// Starts from 0 and goes up with every completed maze.
+    pub level: u32,
+    curve: Handle<ProgressionCurve>,
+    /// The level and seed last picked for the difficulty band, so restarts keep their maze.
+    pub picked: Option<(u32, MazeSeed)>,
+}
+
+impl FromWorld for LevelProgression {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            level: 0,
+            curve: world
+                .resource::<AssetServer>()
+                .load("levels.progression.ron"),
+            picked: None,
+        }
+    }
+}
+
+/// How mazes change from level to level.
+///
+/// Levels between two keyframes interpolate their sizes, braid, floors, sight and difficulty, and use
+/// the algorithm of the earlier keyframe. Levels past the last keyframe stay there.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct ProgressionCurve {
+    pub keyframes: Vec<LevelSettings>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LevelSettings {
+    /// The level this keyframe applies from.
+    pub level: u32,
+    pub width: u32,
+    pub height: u32,
+    pub algorithm: MazeAlgorithm,
+    /// Percentage of dead ends opened into loops, see [`MazeConfig::braid`].
+    #[serde(default)]
+    pub braid: u32,
+    /// Floors stacked on top of each other, see [`MazeConfig::floors`].
+    #[serde(default = "crate::maze::generate::default_floors")]
+    pub floors: u32,
+    /// How far the player can see, in tiles. Lower is darker.
+    pub sight_radius: u32,
+    /// Mazes are regenerated until their [`metrics::MazeMetrics::difficulty`] falls inside this band.
+    #[serde(default)]
+    pub difficulty: Option<(f32, f32)>,
+}
+
+impl ProgressionCurve {
+    /// The settings for `level`, `None` if there are no keyframes.
+    pub fn settings(&self, level: u32) -> Option<LevelSettings> {
+        let Some(i) = self.keyframes.iter().position(|k| k.level > level) else {
+            return self.keyframes.last().cloned();
+        };
+        if i == 0 {
+            return self.keyframes.first().cloned();
+        }
+        let (before, after) = (&self.keyframes[i - 1], &self.keyframes[i]);
+
+        let t = (level - before.level) as f32 / (after.level - before.level) as f32;
+        let lerp = |a: u32, b: u32| (a as f32 + (b as f32 - a as f32) * t).round() as u32;
+
+        Some(LevelSettings {
+            level,
+            width: lerp(before.width, after.width),
+            height: lerp(before.height, after.height),
+            algorithm: before.algorithm,
+            braid: lerp(before.braid, after.braid),
+            floors: lerp(before.floors, after.floors),
+            sight_radius: lerp(before.sight_radius, after.sight_radius),
+            difficulty: before
+                .difficulty
+                .zip(after.difficulty)
+                .map(|(a, b)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)),
+        })
+    }
+}
+
+impl LevelSettings {
+    pub fn difficulty_band(&self) -> Option<RangeInclusive<f32>> {
+        self.difficulty.map(|(min, max)| min..=max)
+    }
+}
+
+#[derive(Debug)]
+pub enum ProgressionError {
+    Io(io::Error),
+    Ron(ron::error::SpannedError),
+    /// Keyframes have to be listed in order of increasing level.
+    Unordered,
+}
+
+impl fmt::Display for ProgressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read progression: {e}"),
+            Self::Ron(e) => write!(f, "failed to parse progression: {e}"),
+            Self::Unordered => write!(f, "progression keyframes are not in level order"),
+        }
+    }
+}
+
+impl Error for ProgressionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Ron(e) => Some(e),
+            Self::Unordered => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProgressionError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for ProgressionError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        Self::Ron(e)
+    }
+}
+
+#[derive(Default)]
+pub struct ProgressionCurveLoader;
+
+impl AssetLoader for ProgressionCurveLoader {
+    type Asset = ProgressionCurve;
+    type Settings = ();
+    type Error = ProgressionError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<ProgressionCurve, ProgressionError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let curve: ProgressionCurve = ron::de::from_bytes(&bytes)?;
+        if !curve.keyframes.windows(2).all(|k| k[0].level < k[1].level) {
+            return Err(ProgressionError::Unordered);
+        }
+
+        Ok(curve)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["progression.ron"]
+    }
+}
+
+fn reset_progression(mut progression: ResMut<LevelProgression>) {
+    progression.level = 0;
+}
+
+fn next_level(mut progression: ResMut<LevelProgression>) {
+    progression.level += 1;
+}
+
+/// Configures the maze for the current level before it is generated.
+fn apply_progression(
+    mut progression: ResMut<LevelProgression>,
+    curves: Res<Assets<ProgressionCurve>>,
+    mut config: ResMut<MazeConfig>,
+    mut seed: ResMut<MazeSeed>,
+    mut sight: ResMut<SightRadius>,
+    pending_save: Option<Res<PendingSave>>,
+    custom: Res<CustomMaze>,
+) {
+    let Some(settings) = curves
+        .get(&progression.curve)
+        .and_then(|curve| curve.settings(progression.level))
+    else {
+        warn!("no progression loaded, keeping the current maze config");
+        return;
+    };
+
+    sight.0 = settings.sight_radius;
+    // A save being loaded brings its own config and seed, and hand authored mazes don't use
+    // either.
+    if pending_save.is_some() || custom.0.is_some() {
+        return;
+    }
+
+    config.width = settings.width.max(3);
+    config.height = settings.height.max(3);
+    config.algorithm = settings.algorithm;
+    config.braid = settings.braid;
+    config.floors = settings.floors;
+
+    let Some(band) = settings.difficulty_band() else {
+        return;
+    };
+    if progression.picked == Some((progression.level, *seed)) {
+        return;
+    }
+
+    let (picked, metrics) = metrics::find_seed(&config, *seed, band, DIFFICULTY_ATTEMPTS);
+    info!(
+        "level {} seed {picked} has difficulty {:.2}",
+        progression.level + 1,
+        metrics.difficulty()
+    );
+    *seed = picked;
+    progression.picked = Some((progression.level, picked));
+}
@@ -0,0 +1,303 @@
+use std::{fs, io, path::PathBuf};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    maze::{
+        floors::ChangeFloor, fog, CustomMaze, FogOfWar, LevelTimer, MazeAsset, MazeConfig,
+        MazeFloor, MazeFloors, MazeSeed,
+    },
+    persist,
+    player::Player,
+    progression::LevelProgression,
+    state::AppState,
+    stats::RunStats,
+};
+
+const SAVE_FILE: &str = "save.ron";
+/// Bumped whenever [`SaveGame`] changes in a way older saves can't be read as.
+const SAVE_VERSION: u32 = 1;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            apply_save.run_if(resource_exists::<PendingSave>),
+        )
+        .add_systems(
+            Update,
+            (
+                save_game.run_if(in_state(AppState::Playing).or_else(in_state(AppState::Paused))),
+                load_game.run_if(
+                    in_state(AppState::Menu)
+                        .or_else(in_state(AppState::Playing))
+                        .or_else(in_state(AppState::Paused)),
+                ),
+            ),
+        );
+    }
+}
+
+/// Everything needed to pick a maze back up where it was left.
+///
+/// The maze itself is not stored, generation is deterministic so the seed and config
+/// rebuild the same tiles and colliders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub seed: MazeSeed,
+    pub config: MazeConfig,
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    /// Whether each tile has been seen, see [`FogOfWar::explored`].
+    pub explored: Vec<bool>,
+    pub stats: RunStats,
+    /// See [`LevelProgression`], saves from before progression start on the first level.
+    #[serde(default)]
+    pub level: u32,
+    /// The floor of the maze the player is on, see [`MazeFloors`].
+    #[serde(default)]
+    pub floor: usize,
+    /// The hand authored maze being played, if any. It is stored whole since the file it
+    /// came from may have changed or be gone by the time the save is loaded.
+    #[serde(default)]
+    pub custom: Option<MazeAsset>,
+}
+
+/// Just enough of a save to check its version before reading the rest.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+impl SaveGame {
+    pub fn path() -> Option<PathBuf> {
+        persist::path(SAVE_FILE)
+    }
+
+    pub fn read() -> io::Result<Self> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Reads a save from the contents of a save file, rejecting other versions.
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        let header: SaveHeader =
+            ron::from_str(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if header.version != SAVE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "save version {} is not supported, expected {SAVE_VERSION}",
+                    header.version
+                ),
+            ));
+        }
+
+        ron::from_str(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn write(&self) -> io::Result<()> {
+        persist::write_atomic(SAVE_FILE, self.to_ron()?)
+    }
+
+    /// The contents of a save file, the inverse of [`Self::parse`].
+    pub fn to_ron(&self) -> io::Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)
+    }
+}
+
+/// A save that is applied once its maze has been rebuilt.
+#[derive(Resource)]
+pub struct PendingSave(SaveGame);
+
+#[allow(clippy::too_many_arguments)]
+fn save_game(
+    input: Res<ButtonInput<KeyCode>>,
+    seed: Res<MazeSeed>,
+    config: Res<MazeConfig>,
+    fog: Res<FogOfWar>,
+    stats: Res<RunStats>,
+    progression: Res<LevelProgression>,
+    floors: Res<MazeFloors>,
+    custom: Res<CustomMaze>,
+    mazes: Res<Assets<MazeAsset>>,
+    player: Query<(&Transform, &LinearVelocity), With<Player>>,
+) {
+    if !input.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    let Some((transform, velocity)) = player.iter().next() else {
+        return;
+    };
+
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        seed: *seed,
+        config: config.clone(),
+        position: transform.translation.truncate().to_array(),
+        velocity: velocity.0.to_array(),
+        explored: fog.explored().to_vec(),
+        stats: stats.clone(),
+        level: progression.level,
+        floor: floors.current(),
+        custom: custom.get(&mazes).cloned(),
+    };
+
+    match save.write() {
+        Ok(()) => info!("saved maze {}", *seed),
+        Err(e) => warn!("failed to save: {e}"),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn load_game(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    mut seed: ResMut<MazeSeed>,
+    mut config: ResMut<MazeConfig>,
+    mut progression: ResMut<LevelProgression>,
+    mut custom: ResMut<CustomMaze>,
+    mut mazes: ResMut<Assets<MazeAsset>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !input.just_pressed(KeyCode::F9) {
+        return;
+    }
+
+    let save = match SaveGame::read() {
+        Ok(save) => save,
+        Err(e) => {
+            warn!("failed to load save: {e}");
+            return;
+        }
+    };
+
+    info!("loading maze {}", save.seed);
+    *seed = save.seed;
+    *config = save.config.clone();
+    progression.level = save.level;
+    // The saved seed was already picked for this level, so restarts keep it too.
+    progression.picked = Some((save.level, save.seed));
+    // Play the saved hand authored maze, or go back to generating if the save has none.
+    custom.0 = save.custom.clone().map(|maze| mazes.add(maze));
+    commands.insert_resource(PendingSave(save));
+    next_state.set(AppState::Loading);
+}
+
+/// Puts the player, fog and stats back once the saved maze has been spawned.
+#[allow(clippy::too_many_arguments)]
+fn apply_save(
+    mut commands: Commands,
+    save: Res<PendingSave>,
+    mut fog: ResMut<FogOfWar>,
+    mut stats: ResMut<RunStats>,
+    mut timer: ResMut<LevelTimer>,
+    mut player: Query<(&mut Transform, &mut LinearVelocity), With<Player>>,
+    storage: Query<(&MazeFloor, &TileStorage)>,
+    mut tiles: Query<(&mut TileVisible, &mut TileColor)>,
+    mut changes: EventWriter<ChangeFloor>,
+) {
+    commands.remove_resource::<PendingSave>();
+    let save = &save.0;
+
+    let position = Vec2::from_array(save.position);
+    if let Some((mut transform, mut velocity)) = player.iter_mut().next() {
+        transform.translation = position.extend(transform.translation.z);
+        velocity.0 = Vec2::from_array(save.velocity);
+    }
+    if save.floor != 0 {
+        changes.send(ChangeFloor {
+            floor: save.floor,
+            position,
+        });
+    }
+
+    if fog.restore(save.explored.clone()) {
+        for (floor, storage) in storage.iter() {
+            fog::show_explored(&fog, **floor, storage, &mut tiles);
+        }
+    } else {
+        warn!("saved fog does not match the maze, ignoring it");
+    }
+
+    *stats = save.stats.clone();
+    timer.set_elapsed(stats.elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save() -> SaveGame {
+        SaveGame {
+            version: SAVE_VERSION,
+            seed: MazeSeed(42),
+            config: MazeConfig {
+                braid: 30,
+                floors: 2,
+                ..Default::default()
+            },
+            position: [12.5, -4.],
+            velocity: [1., 0.],
+            explored: vec![true, false, true],
+            stats: RunStats::default(),
+            level: 3,
+            floor: 1,
+            custom: None,
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let contents = save().to_ron().unwrap();
+        let loaded = SaveGame::parse(&contents).unwrap();
+
+        assert_eq!(loaded.seed, MazeSeed(42));
+        assert_eq!(loaded.config, save().config);
+        assert_eq!(loaded.position, [12.5, -4.]);
+        assert_eq!(loaded.velocity, [1., 0.]);
+        assert_eq!(loaded.explored, [true, false, true]);
+        assert_eq!((loaded.level, loaded.floor), (3, 1));
+        assert_eq!(loaded.to_ron().unwrap(), contents);
+    }
+
+    #[test]
+    fn keeps_custom_mazes() {
+        let maze = crate::maze::import::parse_ascii("S.*\n#.E").unwrap();
+        let contents = SaveGame {
+            custom: Some(maze.clone()),
+            ..save()
+        }
+        .to_ron()
+        .unwrap();
+
+        let loaded = SaveGame::parse(&contents).unwrap().custom.unwrap();
+        assert_eq!(loaded.grid, maze.grid);
+        assert_eq!(loaded.items, maze.items);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        for version in [0, SAVE_VERSION + 1] {
+            let contents = SaveGame { version, ..save() }.to_ron().unwrap();
+            let e = SaveGame::parse(&contents).unwrap_err();
+            assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+            assert!(e.to_string().contains("not supported"), "{e}");
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        let e = SaveGame::parse("not a save").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+}
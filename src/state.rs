@@ -0,0 +1,190 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    animated_sprites::AnimationFinished,
+    maze::{MazeCompleted, MazeSeed},
+    player::DEATH_CLIP,
+    stats::RunStats,
+};
+
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .enable_state_scoped_entities::<AppState>()
+            .add_systems(OnEnter(AppState::Menu), spawn_menu_screen)
+            .add_systems(OnEnter(AppState::Loading), finish_loading)
+            .add_systems(OnEnter(AppState::Playing), unpause_physics)
+            .add_systems(OnExit(AppState::Playing), pause_physics)
+            .add_systems(OnEnter(AppState::Paused), spawn_paused_screen)
+            .add_systems(
+                OnEnter(AppState::LevelComplete),
+                spawn_level_complete_screen,
+            )
+            .add_systems(OnExit(AppState::LevelComplete), roll_seed)
+            .add_systems(OnExit(AppState::GameOver), roll_seed)
+            .add_systems(
+                Update,
+                (
+                    transition_on_key(KeyCode::Enter, AppState::Loading).run_if(
+                        in_state(AppState::Menu).or_else(in_state(AppState::LevelComplete)),
+                    ),
+                    transition_on_key(KeyCode::KeyL, AppState::Leaderboard)
+                        .run_if(in_state(AppState::Menu)),
+                    transition_on_key(KeyCode::Escape, AppState::Menu)
+                        .run_if(in_state(AppState::Leaderboard)),
+                    (
+                        transition_on_key(KeyCode::Enter, AppState::Menu),
+                        spawn_game_over_screen,
+                    )
+                        .run_if(in_state(AppState::GameOver)),
+                    (
+                        transition_on_key(KeyCode::Escape, AppState::Paused),
+                        transition_on_key(KeyCode::KeyR, AppState::Loading),
+                        complete_level,
+                    )
+                        .run_if(in_state(AppState::Playing)),
+                    (
+                        transition_on_key(KeyCode::Escape, AppState::Playing),
+                        transition_on_key(KeyCode::KeyQ, AppState::GameOver),
+                    )
+                        .run_if(in_state(AppState::Paused)),
+                ),
+            );
+    }
+}
+
+/// The top level flow of the game.
+///
+/// Entering [`AppState::Loading`] (re)generates the maze for the current [`MazeSeed`]
+/// and respawns the player, physics only runs while [`AppState::Playing`].
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Loading,
+    Playing,
+    Paused,
+    LevelComplete,
+    GameOver,
+    Leaderboard,
+}
+
+fn transition_on_key(
+    key: KeyCode,
+    state: AppState,
+) -> impl FnMut(Res<ButtonInput<KeyCode>>, ResMut<NextState<AppState>>) {
+    move |input, mut next_state| {
+        if input.just_pressed(key) {
+            next_state.set(state);
+        }
+    }
+}
+
+fn finish_loading(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Playing);
+}
+
+fn complete_level(
+    mut completed: EventReader<MazeCompleted>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if completed.read().last().is_some() {
+        next_state.set(AppState::LevelComplete);
+    }
+}
+
+fn pause_physics(mut time: ResMut<Time<Physics>>) {
+    time.pause();
+}
+
+fn unpause_physics(mut time: ResMut<Time<Physics>>) {
+    time.unpause();
+}
+
+fn roll_seed(mut seed: ResMut<MazeSeed>) {
+    *seed = MazeSeed::random();
+}
+
+/// Spawns centered text that is despawned once `state` is left.
+pub fn spawn_screen(commands: &mut Commands, state: AppState, text: impl Into<String>) {
+    commands
+        .spawn((
+            StateScoped(state),
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 48.,
+                        ..Default::default()
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            );
+        });
+}
+
+fn spawn_menu_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        AppState::Menu,
+        "maze-lite\n\npress enter to start\nf9 to load\nl for best times",
+    );
+}
+
+fn spawn_paused_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        AppState::Paused,
+        "paused\n\nescape to resume\nf5 to save, f9 to load\nq to give up",
+    );
+}
+
+fn spawn_level_complete_screen(mut commands: Commands, stats: Res<RunStats>) {
+    let penalty = if stats.penalty.is_zero() {
+        String::new()
+    } else {
+        format!(" (+{}s hint penalty)", stats.penalty.as_secs())
+    };
+
+    spawn_screen(
+        &mut commands,
+        AppState::LevelComplete,
+        format!(
+            "level complete in {:.2}s{penalty}\n\
+            walked {:.0} tiles, {} restarts, {} hints\n\n\
+            press enter for the next maze",
+            stats.total_time().as_secs_f32(),
+            stats.distance,
+            stats.restarts,
+            stats.hints,
+        ),
+    );
+}
+
+/// Shows the game over screen once the player has finished dying.
+fn spawn_game_over_screen(mut commands: Commands, mut finished: EventReader<AnimationFinished>) {
+    if !finished.read().any(|finished| finished.clip == DEATH_CLIP) {
+        return;
+    }
+
+    spawn_screen(
+        &mut commands,
+        AppState::GameOver,
+        "game over\n\npress enter to return to the menu",
+    );
+}
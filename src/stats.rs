@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    maze::{LevelTimer, MazeConfig, MazeSeed, SpawnMaze},
+    player::Player,
+    state::AppState,
+};
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_systems(OnEnter(AppState::Loading), start_attempt.after(SpawnMaze))
+            .add_systems(Update, track_stats.run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Statistics for the current level.
+///
+/// Restarting a level keeps its restart count, everything else is per attempt.
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    pub seed: Option<MazeSeed>,
+    pub restarts: u32,
+    pub elapsed: Duration,
+    /// Distance walked in tiles.
+    pub distance: f32,
+    pub hints: u32,
+    /// Time added to the level time for using hints.
+    pub penalty: Duration,
+    #[serde(skip)]
+    last_position: Option<Vec2>,
+}
+
+impl RunStats {
+    /// The level time including any penalties.
+    pub fn total_time(&self) -> Duration {
+        self.elapsed + self.penalty
+    }
+}
+
+/// Entering [`AppState::Loading`] with the same seed is a restart, anything else is a new level.
+fn start_attempt(mut stats: ResMut<RunStats>, seed: Res<MazeSeed>) {
+    if stats.seed == Some(*seed) {
+        *stats = RunStats {
+            seed: stats.seed,
+            restarts: stats.restarts + 1,
+            ..Default::default()
+        };
+    } else {
+        *stats = RunStats {
+            seed: Some(*seed),
+            ..Default::default()
+        };
+    }
+}
+
+fn track_stats(
+    mut stats: ResMut<RunStats>,
+    timer: Res<LevelTimer>,
+    config: Res<MazeConfig>,
+    player: Query<&Transform, With<Player>>,
+) {
+    stats.elapsed = timer.elapsed();
+
+    let Some(position) = player.iter().next().map(|t| t.translation.truncate()) else {
+        return;
+    };
+
+    if let Some(last) = stats.last_position {
+        stats.distance += last.distance(position) / config.tile_size;
+    }
+    stats.last_position = Some(position);
+}